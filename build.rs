@@ -0,0 +1,353 @@
+//! Generates the static level catalog for every game version from `data/<version>/*.json`,
+//! the cross version level mapping from `data/mapping.json`
+//! and the translated chapter names from `data/chapter_names.json`
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    fmt::Write,
+    fs,
+    path::Path,
+};
+
+const VERSIONS: &[&str] = &["classic", "ultra"];
+
+/// Every `NameLang` tag, translations are keyed by these
+const LANGS: &[&str] = &[
+    "en", "es", "fr", "de", "it", "jp", "ar", "zh-CN", "zh-TW", "nl", "ko", "pt", "ru", "tr",
+];
+
+/// `metadata.json` field => `LevelMetadata` field, with the type it holds
+const METADATA_FIELDS: &[(&str, &str, &str)] = &[
+    ("par", "par_time", "f32"),
+    ("gold", "gold_time", "f32"),
+    ("diamond", "diamond_time", "f32"),
+    ("author", "author_time", "f32"),
+    ("difficulty", "difficulty", "u8"),
+    ("gems", "gems", "u32"),
+    ("timeTravels", "time_travels", "u32"),
+    ("tutorial", "tutorial", "bool"),
+];
+
+fn main() {
+    println!("cargo:rerun-if-changed=data");
+
+    let mapping: Vec<HashMap<String, String>> = read("mapping.json");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    for version in VERSIONS {
+        let other = VERSIONS.iter().find(|v| *v != version).unwrap();
+        let equivalents = mapping
+            .iter()
+            .map(|pair| (pair[*version].clone(), pair[*other].clone()))
+            .collect::<Vec<(String, String)>>();
+
+        let code = generate(version, other, &equivalents);
+        fs::write(Path::new(&out_dir).join(format!("{version}.rs")), code).unwrap();
+    }
+
+    let code = generate_chapter_names();
+    fs::write(Path::new(&out_dir).join("chapter_names.rs"), code).unwrap();
+}
+
+fn read<T: serde::de::DeserializeOwned>(file: &str) -> T {
+    let path = format!("data/{file}");
+    let json = fs::read_to_string(&path).unwrap_or_else(|err| panic!("{path}: {err}"));
+    serde_json::from_str(&json).unwrap_or_else(|err| panic!("{path}: {err}"))
+}
+
+/// `learning_to_roll_update` => `LearningToRollUpdate`, `3divide` => `Level3divide`
+fn variant(id: &str) -> String {
+    let mut variant = String::new();
+    for part in id.split('_').filter(|part| !part.is_empty()) {
+        let mut chars = part.chars();
+        variant.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+        variant.push_str(chars.as_str());
+    }
+
+    match variant.starts_with(|c: char| c.is_ascii_digit()) {
+        true => format!("Level{variant}"),
+        false => variant,
+    }
+}
+
+fn generate(version: &str, other: &str, equivalents: &[(String, String)]) -> String {
+    let levels: Vec<String> = read(&format!("{version}/levels.json"));
+    let names: HashMap<String, String> = read(&format!("{version}/names.json"));
+    let chapters: serde_json::Map<String, serde_json::Value> =
+        read(&format!("{version}/chapters.json"));
+    let other_levels: Vec<String> = read(&format!("{other}/levels.json"));
+    let metadata: BTreeMap<String, serde_json::Map<String, serde_json::Value>> =
+        read(&format!("{version}/metadata.json"));
+    let translations = LANGS
+        .iter()
+        .filter(|lang| Path::new(&format!("data/{version}/names/{lang}.json")).exists())
+        .map(|lang| {
+            let names: BTreeMap<String, String> = read(&format!("{version}/names/{lang}.json"));
+            (*lang, names)
+        })
+        .collect::<Vec<(&str, BTreeMap<String, String>)>>();
+    if let Ok(dir) = fs::read_dir(format!("data/{version}/names")) {
+        for file in dir {
+            let file = file.unwrap().file_name().into_string().unwrap();
+            if !LANGS.iter().any(|lang| format!("{lang}.json") == file) {
+                panic!("{version}/names/{file}: not a NameLang tag");
+            }
+        }
+    }
+
+    let chapters = chapters
+        .into_iter()
+        .map(|(label, ids)| {
+            let ids: Vec<String> = serde_json::from_value(ids).unwrap();
+            (label.replace(' ', ""), ids)
+        })
+        .collect::<Vec<(String, Vec<String>)>>();
+
+    let mut variants = HashMap::new();
+    for id in &levels {
+        if let Some(other) = variants.insert(variant(id), id) {
+            panic!(
+                "{version}: `{id}` and `{other}` both become `{}`",
+                variant(id)
+            );
+        }
+        if !names.contains_key(id) {
+            panic!("{version}: `{id}` has no name in names.json");
+        }
+    }
+    for (chapter, ids) in &chapters {
+        for id in ids {
+            if !levels.contains(id) {
+                panic!("{version}: `{id}` in {chapter} is not in levels.json");
+            }
+        }
+    }
+    let mut mapped = HashMap::new();
+    for (id, other_id) in equivalents {
+        if !levels.contains(id) || !other_levels.contains(other_id) {
+            panic!("mapping.json: `{id}` => `{other_id}` is not a {version} => {other} level");
+        }
+        if let Some(previous) = mapped.insert(id, other_id) {
+            panic!(
+                "mapping.json: {version} `{id}` is mapped to both `{previous}` and `{other_id}`"
+            );
+        }
+    }
+    for (id, fields) in &metadata {
+        if !levels.contains(id) {
+            panic!("{version}/metadata.json: `{id}` is not in levels.json");
+        }
+        for field in fields.keys() {
+            if !METADATA_FIELDS.iter().any(|(name, ..)| name == field) {
+                panic!("{version}/metadata.json: `{id}` has an unknown field `{field}`");
+            }
+        }
+    }
+    for (lang, names) in &translations {
+        for id in names.keys() {
+            if !levels.contains(id) {
+                panic!("{version}/names/{lang}.json: `{id}` is not in levels.json");
+            }
+        }
+    }
+    let chapter_of = |id: &str| {
+        chapters
+            .iter()
+            .find(|(_, ids)| ids.iter().any(|i| i == id))
+            .map(|(chapter, _)| chapter.as_str())
+    };
+
+    let mut code =
+        String::from("// Generated by build.rs from the json files in data/, do not edit\n\n");
+
+    code.push_str("/// Every level in this version of the game\n");
+    code.push_str("///\n/// Serializes as the level id, without `SP_`\n");
+    code.push_str("#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]\n");
+    code.push_str("pub enum Level {\n");
+    for id in &levels {
+        writeln!(code, "    /// {}", names[id]).unwrap();
+        writeln!(code, "    #[serde(rename = {id:?})]").unwrap();
+        writeln!(code, "    {},", variant(id)).unwrap();
+    }
+    code.push_str("}\n\n");
+
+    code.push_str("impl Level {\n");
+    code.push_str("    /// Every level, in the order of `levels.json`\n");
+    code.push_str("    pub const ALL: &'static [Level] = &[\n");
+    for id in &levels {
+        writeln!(code, "        Level::{},", variant(id)).unwrap();
+    }
+    code.push_str("    ];\n\n");
+
+    code.push_str("    /// The level id, without `SP_`\n");
+    code.push_str("    pub const fn id(&self) -> &'static str {\n        match self {\n");
+    for id in &levels {
+        writeln!(code, "            Level::{} => {id:?},", variant(id)).unwrap();
+    }
+    code.push_str("        }\n    }\n\n");
+
+    code.push_str("    /// The human readable name\n");
+    code.push_str("    pub const fn name(&self) -> &'static str {\n        match self {\n");
+    for id in &levels {
+        writeln!(
+            code,
+            "            Level::{} => {:?},",
+            variant(id),
+            names[id]
+        )
+        .unwrap();
+    }
+    code.push_str("        }\n    }\n\n");
+
+    code.push_str(
+        "    /// The name in another language, falls back to the English [`Level::name`]\n",
+    );
+    code.push_str("    ///\n    /// Translations are read from `names/<lang>.json`\n");
+    code.push_str("    pub fn get_name(&self, lang: NameLang) -> &'static str {\n");
+    code.push_str("        const TRANSLATIONS: &[(&str, &[(Level, &str)])] = &[\n");
+    for (lang, names) in &translations {
+        writeln!(code, "            ({lang:?}, &[").unwrap();
+        for (id, name) in names {
+            writeln!(code, "                (Level::{}, {name:?}),", variant(id)).unwrap();
+        }
+        code.push_str("            ]),\n");
+    }
+    code.push_str("        ];\n\n");
+    code.push_str("        TRANSLATIONS\n");
+    code.push_str("            .iter()\n");
+    code.push_str("            .filter(|(tag, _)| *tag == lang.as_str())\n");
+    code.push_str("            .flat_map(|(_, names)| names.iter())\n");
+    code.push_str("            .find(|(level, _)| level == self)\n");
+    code.push_str("            .map_or(self.name(), |(_, name)| *name)\n");
+    code.push_str("    }\n\n");
+
+    code.push_str("    /// The chapter the level is in, `None` if it isn't in any chapter\n");
+    code.push_str("    pub const fn chapter(&self) -> Option<Chapter> {\n        match self {\n");
+    for id in &levels {
+        match chapter_of(id) {
+            Some(chapter) => writeln!(
+                code,
+                "            Level::{} => Some(Chapter::{chapter}),",
+                variant(id)
+            ),
+            None => writeln!(code, "            Level::{} => None,", variant(id)),
+        }
+        .unwrap();
+    }
+    code.push_str("        }\n    }\n\n");
+
+    writeln!(
+        code,
+        "    /// The remake or original of this level in {other}, from `data/mapping.json`"
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "    pub const fn {other}_equivalent(&self) -> Option<crate::data::{other}::Level> {{"
+    )
+    .unwrap();
+    code.push_str("        match self {\n");
+    for (id, other_id) in equivalents {
+        writeln!(
+            code,
+            "            Level::{} => Some(crate::data::{other}::Level::{}),",
+            variant(id),
+            variant(other_id)
+        )
+        .unwrap();
+    }
+    code.push_str("            _ => None,\n        }\n    }\n\n");
+
+    code.push_str("    /// Par times, gem counts and such, from `metadata.json`\n");
+    code.push_str("    pub const fn metadata(&self) -> LevelMetadata {\n        match self {\n");
+    for (id, fields) in &metadata {
+        writeln!(
+            code,
+            "            Level::{} => LevelMetadata {{",
+            variant(id)
+        )
+        .unwrap();
+        for (name, field, kind) in METADATA_FIELDS {
+            let Some(raw) = fields.get(*name) else {
+                let missing = if *kind == "bool" { "false" } else { "None" };
+                writeln!(code, "                {field}: {missing},").unwrap();
+                continue;
+            };
+            let integer = |max: u64| raw.as_u64().filter(|value| *value <= max);
+            let value = match (*kind, raw) {
+                ("bool", serde_json::Value::Bool(value)) => Some(value.to_string()),
+                ("f32", serde_json::Value::Number(value)) => {
+                    value.as_f64().map(|value| format!("Some({value:?})"))
+                }
+                ("u8", _) => integer(u8::MAX.into()).map(|value| format!("Some({value})")),
+                ("u32", _) => integer(u32::MAX.into()).map(|value| format!("Some({value})")),
+                _ => None,
+            }
+            .unwrap_or_else(|| {
+                panic!("{version}/metadata.json: `{id}.{name}` should be a {kind}, not `{raw}`")
+            });
+            writeln!(code, "                {field}: {value},").unwrap();
+        }
+        code.push_str("            },\n");
+    }
+    code.push_str("            _ => LevelMetadata::UNKNOWN,\n        }\n    }\n\n");
+
+    code.push_str("    /// Finds a level from its id, with or without `SP_`\n");
+    code.push_str("    pub fn from_id(id: &str) -> Option<Level> {\n");
+    code.push_str("        match id.strip_prefix(\"SP_\").unwrap_or(id) {\n");
+    for id in &levels {
+        writeln!(code, "            {id:?} => Some(Level::{}),", variant(id)).unwrap();
+    }
+    code.push_str("            _ => None,\n        }\n    }\n}\n\n");
+
+    code.push_str("impl Chapter {\n");
+    code.push_str("    /// The levels in this chapter, in order\n");
+    code.push_str("    pub const fn levels(&self) -> &'static [Level] {\n        match self {\n");
+    for (chapter, ids) in &chapters {
+        let ids = ids
+            .iter()
+            .map(|id| format!("Level::{}", variant(id)))
+            .collect::<Vec<String>>()
+            .join(", ");
+        writeln!(code, "            Chapter::{chapter} => &[{ids}],").unwrap();
+    }
+    code.push_str("        }\n    }\n}\n");
+
+    code
+}
+
+fn generate_chapter_names() -> String {
+    let names: BTreeMap<String, HashMap<String, String>> = read("chapter_names.json");
+
+    if !names.contains_key("en") {
+        panic!("chapter_names.json: English is required as the fallback");
+    }
+    for (lang, formats) in &names {
+        if !LANGS.contains(&lang.as_str()) {
+            panic!("chapter_names.json: `{lang}` is not a NameLang tag");
+        }
+        for kind in ["chapter", "bonus"] {
+            match formats.get(kind) {
+                Some(format) if format.contains("{}") => (),
+                _ => panic!("chapter_names.json: `{lang}.{kind}` needs a `{{}}` for the number"),
+            }
+        }
+    }
+
+    let mut code =
+        String::from("// Generated by build.rs from data/chapter_names.json, do not edit\n\n");
+    code.push_str("/// `(lang, chapter format, bonus format)`, `{}` is replaced with the number\n");
+    code.push_str("const CHAPTER_NAMES: &[(&str, &str, &str)] = &[\n");
+    for (lang, formats) in &names {
+        writeln!(
+            code,
+            "    ({lang:?}, {:?}, {:?}),",
+            formats["chapter"], formats["bonus"]
+        )
+        .unwrap();
+    }
+    code.push_str("];\n");
+
+    code
+}
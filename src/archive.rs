@@ -0,0 +1,368 @@
+//! A local history of weekly challenges
+//!
+//! The backend only keeps the current and previous challenge, so every fetched [`Weekly`]
+//! can be added to an [`Archive`] and saved as JSON Lines, one [`ArchivedChallenge`] per line.
+//!
+//! ```no_run
+//! use miu::{GameVersion, Weekly, archive::Archive};
+//!
+//! # fn fetch() -> Weekly { todo!() }
+//! let mut archive = Archive::load("weekly.jsonl").unwrap();
+//! archive.add_weekly(GameVersion::Ultra, &fetch());
+//! archive.save("weekly.jsonl").unwrap();
+//!
+//! for entry in archive.with_mod("gravity") {
+//!     let challenge = &entry.challenge;
+//!     println!("{}: {:?}", challenge.challenge_id, challenge.get_name(miu::NameLang::En));
+//! }
+//! ```
+
+use std::{
+    fs::{self, File},
+    io::{BufRead, BufReader, BufWriter, ErrorKind, Write},
+    path::Path,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{Challenge, GameVersion, MIUError, MapId, Weekly};
+
+/// Weekly challenges, unique by version and `challenge_id`, oldest first
+///
+/// Classic and ultra number their challenges separately, so the same id can be in both.
+#[derive(Debug, Clone, Default)]
+pub struct Archive {
+    challenges: Vec<ArchivedChallenge>,
+}
+
+/// A [`Challenge`] together with the version of the game it's from, one line of the archive
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ArchivedChallenge {
+    /// The version of the game
+    pub version: GameVersion,
+    /// The challenge
+    #[serde(flatten)]
+    pub challenge: Challenge,
+}
+
+impl Archive {
+    /// Creates an empty archive
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads an archive from a JSON Lines file, a missing file is an empty archive
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, MIUError> {
+        let mut archive = Archive::new();
+        match File::open(path) {
+            Ok(file) => {
+                archive.import_jsonl(BufReader::new(file))?;
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => (),
+            Err(err) => return Err(MIUError::Io(err)),
+        }
+
+        Ok(archive)
+    }
+
+    /// Writes the archive to a JSON Lines file, replacing it
+    ///
+    /// The archive is written to `<path>.tmp` first and then renamed over `path`,
+    /// so a failed write leaves the old file as it was.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), MIUError> {
+        let path = path.as_ref();
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+
+        let write = || {
+            let mut writer = BufWriter::new(File::create(&temp).map_err(MIUError::Io)?);
+            self.export_jsonl(&mut writer)?;
+            let file = writer
+                .into_inner()
+                .map_err(|err| MIUError::Io(err.into_error()))?;
+            file.sync_all().map_err(MIUError::Io)?;
+            fs::rename(&temp, path).map_err(MIUError::Io)
+        };
+
+        write().inspect_err(|_| {
+            let _ = fs::remove_file(&temp);
+        })
+    }
+
+    /// Adds a challenge, replacing the one with the same version and `challenge_id`
+    ///
+    /// Returns `true` if the challenge wasn't in the archive yet
+    pub fn insert(&mut self, version: GameVersion, challenge: Challenge) -> bool {
+        let entry = ArchivedChallenge { version, challenge };
+        let existing = self.challenges.iter_mut().find(|existing| {
+            existing.version == version
+                && existing.challenge.challenge_id == entry.challenge.challenge_id
+        });
+
+        let added = match existing {
+            Some(existing) => {
+                *existing = entry;
+                false
+            }
+            None => {
+                self.challenges.push(entry);
+                true
+            }
+        };
+        self.sort();
+        added
+    }
+
+    /// Adds the current and previous challenge of a weekly, returns how many were new
+    pub fn add_weekly(&mut self, version: GameVersion, weekly: &Weekly) -> usize {
+        [
+            &weekly.score_buckets.previous,
+            &weekly.score_buckets.current,
+        ]
+        .into_iter()
+        .filter(|challenge| self.insert(version, (*challenge).clone()))
+        .count()
+    }
+
+    /// Returns the challenge of a version with an id
+    pub fn get(&self, version: GameVersion, challenge_id: &str) -> Option<&Challenge> {
+        self.challenges
+            .iter()
+            .find(|entry| entry.version == version && entry.challenge.challenge_id == challenge_id)
+            .map(|entry| &entry.challenge)
+    }
+
+    /// The amount of challenges
+    pub fn len(&self) -> usize {
+        self.challenges.len()
+    }
+
+    /// If the archive has no challenges
+    pub fn is_empty(&self) -> bool {
+        self.challenges.is_empty()
+    }
+
+    /// Every challenge, oldest first
+    pub fn iter(&self) -> std::slice::Iter<'_, ArchivedChallenge> {
+        self.challenges.iter()
+    }
+
+    /// Every challenge of one version of the game, oldest first
+    pub fn of_version(&self, version: GameVersion) -> impl Iterator<Item = &ArchivedChallenge> {
+        self.challenges
+            .iter()
+            .filter(move |entry| entry.version == version)
+    }
+
+    /// Every challenge that was running at some point between `from` and `to`
+    pub fn between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> impl Iterator<Item = &ArchivedChallenge> {
+        self.challenges
+            .iter()
+            .filter(move |entry| entry.challenge.start_date < to && entry.challenge.end_date > from)
+    }
+
+    /// Every challenge with a level, the id can be with or without `SP_`
+    pub fn with_level(&self, level_id: &str) -> impl Iterator<Item = &ArchivedChallenge> {
+        let map = MapId::new(level_id);
+        self.challenges.iter().filter(move |entry| {
+            entry
+                .challenge
+                .levels
+                .iter()
+                .any(|level| level.map() == map)
+        })
+    }
+
+    /// Every challenge where a level changes a physics mod, by its key like `gravity`
+    ///
+    /// Mods set to their default, like `gravity` at `1.0`, don't count, see [`PhysicsMod::is_neutral`](crate::PhysicsMod::is_neutral)
+    pub fn with_mod<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a ArchivedChallenge> {
+        self.challenges.iter().filter(move |entry| {
+            entry.challenge.levels.iter().any(|level| {
+                level
+                    .physicsmod
+                    .get(key)
+                    .is_some_and(|physics_mod| !physics_mod.is_neutral())
+            })
+        })
+    }
+
+    /// Adds every challenge from JSON Lines, one [`ArchivedChallenge`] per line, returns how many were new
+    ///
+    /// Empty lines are skipped
+    pub fn import_jsonl(&mut self, reader: impl BufRead) -> Result<usize, MIUError> {
+        let mut added = 0;
+        for (index, line) in reader.lines().enumerate() {
+            let line = line.map_err(MIUError::Io)?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let entry = serde_json::from_str::<ArchivedChallenge>(&line)
+                .map_err(|err| MIUError::FailedToParseArchive(index + 1, err))?;
+            if self.insert(entry.version, entry.challenge) {
+                added += 1;
+            }
+        }
+
+        Ok(added)
+    }
+
+    /// Writes every challenge as JSON Lines, oldest first
+    pub fn export_jsonl(&self, mut writer: impl Write) -> Result<(), MIUError> {
+        for entry in &self.challenges {
+            serde_json::to_writer(&mut writer, entry).map_err(|err| MIUError::Io(err.into()))?;
+            writeln!(writer).map_err(MIUError::Io)?;
+        }
+
+        Ok(())
+    }
+
+    fn sort(&mut self) {
+        self.challenges.sort_by(|a, b| {
+            a.challenge
+                .start_date
+                .cmp(&b.challenge.start_date)
+                .then_with(|| (a.version as u8).cmp(&(b.version as u8)))
+                .then_with(|| a.challenge.challenge_id.cmp(&b.challenge.challenge_id))
+        });
+    }
+}
+
+impl<'a> IntoIterator for &'a Archive {
+    type Item = &'a ArchivedChallenge;
+    type IntoIter = std::slice::Iter<'a, ArchivedChallenge>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.challenges.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{TimeZone, Utc};
+
+    use crate::{
+        GameVersion, MIUError, PhysicsMod, Weekly, archive::Archive, test_util::weekly_json,
+    };
+
+    #[test]
+    fn dedup_and_queries() {
+        let weekly = Weekly::from_json(&weekly_json()).unwrap();
+
+        let mut archive = Archive::new();
+        assert_eq!(2, archive.add_weekly(GameVersion::Ultra, &weekly));
+        assert_eq!(0, archive.add_weekly(GameVersion::Ultra, &weekly));
+        assert_eq!(2, archive.len());
+
+        let ids = archive
+            .iter()
+            .map(|entry| entry.challenge.challenge_id.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(vec!["challenge_1", "challenge_2"], ids);
+
+        // Classic numbers its challenges separately
+        assert_eq!(2, archive.add_weekly(GameVersion::Classic, &weekly));
+        assert_eq!(4, archive.len());
+        assert_eq!(2, archive.of_version(GameVersion::Classic).count());
+        assert!(archive.get(GameVersion::Classic, "challenge_1").is_some());
+
+        let mut archive = Archive::new();
+        archive.add_weekly(GameVersion::Ultra, &weekly);
+        let day = |day| Utc.with_ymd_and_hms(2024, 1, day, 12, 0, 0).unwrap();
+        assert_eq!(1, archive.between(day(2), day(3)).count());
+        assert_eq!(2, archive.between(day(7), day(9)).count());
+        assert_eq!(0, archive.between(day(20), day(21)).count());
+
+        assert_eq!(1, archive.with_level("greatWall").count());
+        assert_eq!(1, archive.with_level("SP_duality_v2").count());
+        assert_eq!(2, archive.with_mod("reverse").count());
+        assert_eq!(
+            Some("challenge_2"),
+            archive
+                .with_mod("gravity")
+                .next()
+                .map(|entry| entry.challenge.challenge_id.as_str())
+        );
+        assert_eq!(0, archive.with_mod("boomerang").count());
+
+        // Mods set to their default don't count as using the mod
+        let mut neutral = weekly.score_buckets.current.clone();
+        neutral.challenge_id = String::from("challenge_3");
+        for level in &mut neutral.levels {
+            level.physicsmod.insert(PhysicsMod::Gravity(1.0));
+            level.physicsmod.insert(PhysicsMod::Boomerang(false));
+        }
+        archive.insert(GameVersion::Ultra, neutral);
+        assert_eq!(1, archive.with_mod("gravity").count());
+        assert_eq!(0, archive.with_mod("boomerang").count());
+    }
+
+    #[test]
+    fn jsonl() {
+        let weekly = Weekly::from_json(&weekly_json()).unwrap();
+        let mut archive = Archive::new();
+        archive.add_weekly(GameVersion::Ultra, &weekly);
+
+        let mut jsonl = Vec::new();
+        archive.export_jsonl(&mut jsonl).unwrap();
+        let jsonl = String::from_utf8(jsonl).unwrap();
+        assert_eq!(2, jsonl.lines().count());
+        assert!(
+            jsonl
+                .lines()
+                .all(|line| line.contains(r#""version":"Ultra""#))
+        );
+
+        let mut imported = Archive::new();
+        assert_eq!(
+            2,
+            imported
+                .import_jsonl(format!("\n{jsonl}\n").as_bytes())
+                .unwrap()
+        );
+        assert_eq!(0, imported.import_jsonl(jsonl.as_bytes()).unwrap());
+        assert_eq!(
+            archive
+                .get(GameVersion::Ultra, "challenge_2")
+                .unwrap()
+                .levels[0]
+                .physicsmod,
+            imported
+                .get(GameVersion::Ultra, "challenge_2")
+                .unwrap()
+                .levels[0]
+                .physicsmod
+        );
+
+        let broken = format!("{jsonl}{{\"challengeID\": 3}}\n");
+        assert!(matches!(
+            Archive::new().import_jsonl(broken.as_bytes()),
+            Err(MIUError::FailedToParseArchive(3, _))
+        ));
+    }
+
+    #[test]
+    fn save() {
+        let weekly = Weekly::from_json(&weekly_json()).unwrap();
+        let mut archive = Archive::new();
+        archive.add_weekly(GameVersion::Ultra, &weekly);
+
+        let dir = std::env::temp_dir().join(format!("miu-archive-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("weekly.jsonl");
+
+        archive.save(&path).unwrap();
+        archive.save(&path).unwrap();
+        assert!(!dir.join("weekly.jsonl.tmp").exists());
+        assert_eq!(2, Archive::load(&path).unwrap().len());
+        assert!(Archive::load(dir.join("missing.jsonl")).unwrap().is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
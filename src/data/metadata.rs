@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+/// Per level metadata, from `data/<version>/metadata.json`
+///
+/// Every value is optional as the data is still being filled in, `None` means unknown.
+/// No medal times, difficulties or author times have been collected yet,
+/// so for now only `tutorial` is set, [`LevelMetadata::medal`] needs times filled in by hand.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct LevelMetadata {
+    /// The par time in seconds
+    pub par_time: Option<f32>,
+    /// The gold time in seconds
+    pub gold_time: Option<f32>,
+    /// The diamond time in seconds
+    pub diamond_time: Option<f32>,
+    /// The time set by the level author in seconds
+    pub author_time: Option<f32>,
+    /// The difficulty rating shown in game, higher is harder
+    pub difficulty: Option<u8>,
+    /// How many gems the level has
+    pub gems: Option<u32>,
+    /// How many time travels the level has
+    pub time_travels: Option<u32>,
+    /// If the level is a tutorial
+    pub tutorial: bool,
+}
+
+/// The medal a time earns on a level, ordered from worst to best
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Medal {
+    /// Slower than the par time
+    None,
+    /// At or under the par time
+    Par,
+    /// At or under the gold time
+    Gold,
+    /// At or under the diamond time
+    Diamond,
+}
+
+impl LevelMetadata {
+    /// Metadata for a level with nothing known about it
+    pub const UNKNOWN: Self = Self {
+        par_time: None,
+        gold_time: None,
+        diamond_time: None,
+        author_time: None,
+        difficulty: None,
+        gems: None,
+        time_travels: None,
+        tutorial: false,
+    };
+
+    /// Returns the medal a time earns, [`Medal::None`] if it's slower than par
+    ///
+    /// `None` if any medal time isn't known
+    pub fn medal(&self, time: f32) -> Option<Medal> {
+        let (par, gold, diamond) = (self.par_time?, self.gold_time?, self.diamond_time?);
+
+        Some(if time <= diamond {
+            Medal::Diamond
+        } else if time <= gold {
+            Medal::Gold
+        } else if time <= par {
+            Medal::Par
+        } else {
+            Medal::None
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::data::{GameData, LevelMetadata, Medal, classic, ultra};
+
+    #[test]
+    fn medal() {
+        let metadata = LevelMetadata {
+            par_time: Some(30.0),
+            gold_time: Some(20.0),
+            diamond_time: Some(15.0),
+            ..LevelMetadata::UNKNOWN
+        };
+
+        assert_eq!(Some(Medal::Diamond), metadata.medal(15.0));
+        assert_eq!(Some(Medal::Gold), metadata.medal(15.1));
+        assert_eq!(Some(Medal::Par), metadata.medal(29.0));
+        assert_eq!(Some(Medal::None), metadata.medal(30.5));
+        assert!(Medal::Diamond > Medal::Gold);
+        assert!(Medal::Par > Medal::None);
+
+        let partial = LevelMetadata {
+            diamond_time: None,
+            ..metadata
+        };
+        assert_eq!(None, partial.medal(10.0));
+    }
+
+    #[test]
+    fn level_metadata() {
+        let classic = classic::Data::new().unwrap();
+        let ultra = ultra::Data::new().unwrap();
+
+        assert!(classic::Level::RollTutorial.metadata().tutorial);
+        assert!(!classic::Level::SugarRush.metadata().tutorial);
+        assert_eq!(None, classic::Level::SugarRush.metadata().author_time);
+        assert_eq!(None, classic::Level::SugarRush.metadata().difficulty);
+        assert_eq!(
+            Some(true),
+            ultra
+                .metadata("SP_learning_to_roll_update")
+                .map(|m| m.tutorial)
+        );
+        assert_eq!(None, ultra.metadata("rollTutorial"));
+        assert_eq!(None, classic.metadata("SP_sugarRush").unwrap().medal(10.0));
+    }
+}
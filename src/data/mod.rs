@@ -0,0 +1,494 @@
+/// Game data related to the classic version of the game
+pub mod classic;
+mod metadata;
+/// Game data related to the ultra version of the game
+pub mod ultra;
+
+pub use metadata::{LevelMetadata, Medal};
+
+use std::{collections::HashMap, fmt::Debug, hash::Hash};
+
+use crate::{GameVersion, NameLang};
+
+include!(concat!(env!("OUT_DIR"), "/chapter_names.rs"));
+
+/// A chapter in either version of the game
+///
+/// Implemented by [`classic::Chapter`], [`ultra::Chapter`] and [`AnyChapter`]
+pub trait GameChapter: Copy + Eq + Hash + Debug + 'static {
+    /// Every chapter, in the order they appear in game
+    const ALL: &'static [Self];
+
+    /// The version of the game this chapter is in
+    fn version(&self) -> GameVersion;
+
+    /// The name used in the source data, like `Chapter 1` or `Bonus 2`
+    fn label(&self) -> &'static str;
+
+    /// If this is a bonus chapter
+    fn is_bonus(&self) -> bool;
+
+    /// The number of the chapter among chapters of the same kind, `Bonus 2` is `2`
+    fn number(&self) -> u8;
+
+    /// The name in another language, like `Chapitre 1`, falls back to English
+    ///
+    /// Translations are read from `data/chapter_names.json`
+    fn get_name(&self, lang: NameLang) -> String {
+        let (_, chapter, bonus) = CHAPTER_NAMES
+            .iter()
+            .find(|(tag, ..)| *tag == lang.as_str())
+            .or_else(|| CHAPTER_NAMES.iter().find(|(tag, ..)| *tag == "en"))
+            .expect("English is always in chapter_names.json");
+
+        match self.is_bonus() {
+            true => bonus.replace("{}", &self.number().to_string()),
+            false => chapter.replace("{}", &self.number().to_string()),
+        }
+    }
+}
+
+/// Game data for either version of the game, so tools can be written once for both
+///
+/// ```
+/// use miu::data::{GameChapter, GameData, classic, ultra};
+///
+/// fn print_chapters<D: GameData>(data: &D) {
+///     for chapter in D::Chapter::ALL {
+///         println!("{}: {:?}", chapter.label(), data.chapter_levels(*chapter));
+///     }
+/// }
+///
+/// print_chapters(&classic::Data::new().unwrap());
+/// print_chapters(&ultra::Data::new().unwrap());
+/// ```
+pub trait GameData {
+    /// The chapter type for this version
+    type Chapter: GameChapter;
+
+    /// The version of the game this data is for
+    fn version(&self) -> GameVersion;
+
+    /// A list of all level ids, without `SP_`
+    fn levels(&self) -> &[String];
+
+    /// A map of `level id` => `human readable name`
+    fn names(&self) -> &HashMap<String, String>;
+
+    /// The level ids in a chapter, in order, empty if the chapter isn't in this data
+    fn chapter_levels(&self, chapter: Self::Chapter) -> &[String];
+
+    /// Par times, gem counts and such for a level, `None` if the level isn't in this data
+    fn metadata(&self, level: &str) -> Option<LevelMetadata>;
+
+    /// The name of a level in another language, falls back to the English [`GameData::name`]
+    fn get_name(&self, level: &str, lang: NameLang) -> Option<&str>;
+
+    /// The human readable name of a level
+    fn name(&self, level: &str) -> Option<&str> {
+        self.names().get(level).map(String::as_str)
+    }
+
+    /// Every level id in play order, chapter by chapter
+    fn ordered_levels(&self) -> Vec<&str> {
+        Self::Chapter::ALL
+            .iter()
+            .flat_map(|chapter| self.chapter_levels(*chapter))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// The chapter a level is in
+    fn chapter_of(&self, level: &str) -> Option<Self::Chapter> {
+        Self::Chapter::ALL
+            .iter()
+            .find(|chapter| self.chapter_levels(**chapter).iter().any(|l| l == level))
+            .copied()
+    }
+
+    /// The index of a level within its chapter, starting at 0
+    fn position(&self, level: &str) -> Option<usize> {
+        let chapter = self.chapter_of(level)?;
+        self.chapter_levels(chapter).iter().position(|l| l == level)
+    }
+
+    /// The level after this one in play order, continues into the next chapter
+    fn next(&self, level: &str) -> Option<&str> {
+        let levels = self.ordered_levels();
+        let index = levels.iter().position(|l| *l == level)?;
+        levels.get(index + 1).copied()
+    }
+
+    /// The level before this one in play order, continues into the previous chapter
+    fn prev(&self, level: &str) -> Option<&str> {
+        let levels = self.ordered_levels();
+        let index = levels.iter().position(|l| *l == level)?;
+        levels.get(index.checked_sub(1)?).copied()
+    }
+
+    /// Finds a level id from its human readable name
+    ///
+    /// Case, spaces and punctuation are ignored, and small typos or partial names still match.
+    /// Level ids are also accepted.
+    fn level_by_name(&self, name: &str) -> Option<&str> {
+        let wanted = normalize(name);
+        if wanted.is_empty() {
+            return None;
+        }
+
+        let mut candidates = self
+            .names()
+            .iter()
+            .map(|(id, name)| (id.as_str(), normalize(name), normalize(id)))
+            .collect::<Vec<(&str, String, String)>>();
+        candidates.sort_by(|a, b| a.0.cmp(b.0));
+
+        if let Some((id, ..)) = candidates
+            .iter()
+            .find(|(_, name, id)| *name == wanted || *id == wanted)
+        {
+            return Some(id);
+        }
+        if let Some((id, ..)) = candidates
+            .iter()
+            .filter(|(_, name, _)| name.contains(&wanted))
+            .min_by_key(|(_, name, _)| name.len())
+        {
+            return Some(id);
+        }
+
+        let max_distance = (wanted.chars().count() / 4).max(1);
+        candidates
+            .iter()
+            .map(|(id, name, _)| (*id, distance(name, &wanted)))
+            .filter(|(_, distance)| *distance <= max_distance)
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(id, _)| id)
+    }
+}
+
+/// Lowercases and removes everything that isn't a letter or digit
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// The Levenshtein distance between two strings
+fn distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<char>>();
+    let mut row = (0..=b.len()).collect::<Vec<usize>>();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, b) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = (above + 1)
+                .min(row[j] + 1)
+                .min(diagonal + usize::from(a != *b));
+            diagonal = above;
+        }
+    }
+
+    row[b.len()]
+}
+
+/// A chapter from either version of the game, used by [`VersionData`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnyChapter {
+    Classic(classic::Chapter),
+    Ultra(ultra::Chapter),
+}
+
+impl GameChapter for AnyChapter {
+    const ALL: &'static [Self] = &[
+        AnyChapter::Classic(classic::Chapter::Chapter1),
+        AnyChapter::Classic(classic::Chapter::Chapter2),
+        AnyChapter::Classic(classic::Chapter::Chapter3),
+        AnyChapter::Classic(classic::Chapter::Chapter4),
+        AnyChapter::Classic(classic::Chapter::Chapter5),
+        AnyChapter::Classic(classic::Chapter::Chapter6),
+        AnyChapter::Ultra(ultra::Chapter::Chapter1),
+        AnyChapter::Ultra(ultra::Chapter::Chapter2),
+        AnyChapter::Ultra(ultra::Chapter::Chapter3),
+        AnyChapter::Ultra(ultra::Chapter::Chapter4),
+        AnyChapter::Ultra(ultra::Chapter::Chapter5),
+        AnyChapter::Ultra(ultra::Chapter::Chapter6),
+        AnyChapter::Ultra(ultra::Chapter::Bonus1),
+        AnyChapter::Ultra(ultra::Chapter::Bonus2),
+        AnyChapter::Ultra(ultra::Chapter::Bonus3),
+        AnyChapter::Ultra(ultra::Chapter::Bonus4),
+    ];
+
+    fn version(&self) -> GameVersion {
+        match self {
+            AnyChapter::Classic(chapter) => chapter.version(),
+            AnyChapter::Ultra(chapter) => chapter.version(),
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            AnyChapter::Classic(chapter) => chapter.label(),
+            AnyChapter::Ultra(chapter) => chapter.label(),
+        }
+    }
+
+    fn is_bonus(&self) -> bool {
+        match self {
+            AnyChapter::Classic(chapter) => chapter.is_bonus(),
+            AnyChapter::Ultra(chapter) => chapter.is_bonus(),
+        }
+    }
+
+    fn number(&self) -> u8 {
+        match self {
+            AnyChapter::Classic(chapter) => chapter.number(),
+            AnyChapter::Ultra(chapter) => chapter.number(),
+        }
+    }
+}
+
+/// The data of a version picked at runtime, returned by [`GameVersion::data`]
+#[derive(Debug, Clone)]
+pub enum VersionData {
+    Classic(classic::Data),
+    Ultra(ultra::Data),
+}
+
+impl GameData for VersionData {
+    type Chapter = AnyChapter;
+
+    fn version(&self) -> GameVersion {
+        match self {
+            VersionData::Classic(data) => data.version(),
+            VersionData::Ultra(data) => data.version(),
+        }
+    }
+
+    fn levels(&self) -> &[String] {
+        match self {
+            VersionData::Classic(data) => data.levels(),
+            VersionData::Ultra(data) => data.levels(),
+        }
+    }
+
+    fn names(&self) -> &HashMap<String, String> {
+        match self {
+            VersionData::Classic(data) => data.names(),
+            VersionData::Ultra(data) => data.names(),
+        }
+    }
+
+    fn metadata(&self, level: &str) -> Option<LevelMetadata> {
+        match self {
+            VersionData::Classic(data) => data.metadata(level),
+            VersionData::Ultra(data) => data.metadata(level),
+        }
+    }
+
+    fn get_name(&self, level: &str, lang: NameLang) -> Option<&str> {
+        match self {
+            VersionData::Classic(data) => data.get_name(level, lang),
+            VersionData::Ultra(data) => data.get_name(level, lang),
+        }
+    }
+
+    fn chapter_levels(&self, chapter: AnyChapter) -> &[String] {
+        match (self, chapter) {
+            (VersionData::Classic(data), AnyChapter::Classic(chapter)) => {
+                data.chapter_levels(chapter)
+            }
+            (VersionData::Ultra(data), AnyChapter::Ultra(chapter)) => data.chapter_levels(chapter),
+            _ => &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        GameVersion, NameLang,
+        data::{AnyChapter, GameChapter, GameData, classic, ultra},
+    };
+
+    #[test]
+    fn classic_data() {
+        let data = classic::Data::new().unwrap();
+
+        assert!(!data.levels().is_empty());
+        assert!(!data.names().is_empty());
+        assert!(!data.chapters().is_empty());
+
+        assert!(data.chapters().get(&classic::Chapter::Chapter1).is_some());
+        assert!(data.chapters().get(&classic::Chapter::Chapter6).is_some());
+
+        for level in data.levels() {
+            assert!(data.names().get(level).is_some());
+        }
+    }
+
+    #[test]
+    fn ultra_data() {
+        let data = ultra::Data::new().unwrap();
+
+        assert!(!data.levels().is_empty());
+        assert!(!data.names().is_empty());
+        assert!(!data.chapters().is_empty());
+
+        assert!(data.chapters().get(&ultra::Chapter::Chapter1).is_some());
+        assert!(data.chapters().get(&ultra::Chapter::Chapter6).is_some());
+        assert!(data.chapters().get(&ultra::Chapter::Bonus1).is_some());
+        assert!(data.chapters().get(&ultra::Chapter::Bonus4).is_some());
+
+        for level in data.levels() {
+            assert!(data.names().get(level).is_some());
+        }
+    }
+
+    fn chapters_in_order<D: GameData>(data: &D) -> Vec<String> {
+        D::Chapter::ALL
+            .iter()
+            .flat_map(|chapter| data.chapter_levels(*chapter).to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn static_levels() {
+        const SUGAR_RUSH: &str = classic::Level::SugarRush.name();
+        assert_eq!("Sugar Rush", SUGAR_RUSH);
+        assert_eq!("3divide", classic::Level::Level3divide.id());
+        assert_eq!(
+            Some(ultra::Level::LearningToRollUpdate),
+            ultra::Level::from_id("SP_learning_to_roll_update")
+        );
+        assert_eq!(None, ultra::Level::from_id("rollTutorial"));
+
+        let data = classic::Data::default();
+        for level in classic::Level::ALL {
+            assert_eq!(Some(level.name()), data.name(level.id()));
+            assert_eq!(level.chapter(), data.chapter_of(level.id()));
+        }
+        for chapter in ultra::Chapter::ALL {
+            for level in chapter.levels() {
+                assert_eq!(Some(*chapter), level.chapter());
+            }
+        }
+
+        assert_eq!(
+            "\"greatWall\"",
+            serde_json::to_string(&ultra::Level::GreatWall).unwrap()
+        );
+    }
+
+    #[test]
+    fn equivalents() {
+        let classic = classic::Data::new().unwrap();
+        let ultra = ultra::Data::new().unwrap();
+
+        let roll = ultra.classic_equivalent("learning_to_roll_update").unwrap();
+        assert_eq!(classic::Level::RollTutorial, roll);
+        assert_eq!("Learning To Roll", roll.name());
+        assert_eq!(
+            Some(ultra::Level::DualityV2),
+            classic.ultra_equivalent("SP_duality")
+        );
+        assert_eq!(
+            Some(classic::Level::GreatWall),
+            ultra::Level::GreatWall.classic_equivalent()
+        );
+        assert_eq!(None, ultra.classic_equivalent("bunny_slope"));
+
+        for level in classic::Level::ALL {
+            if let Some(remake) = level.ultra_equivalent() {
+                assert_eq!(Some(*level), remake.classic_equivalent());
+            }
+        }
+    }
+
+    #[test]
+    fn lookups() {
+        let data = ultra::Data::new().unwrap();
+
+        assert_eq!(Some(ultra::Chapter::Chapter2), data.chapter_of("greatWall"));
+        assert_eq!(Some(2), data.position("greatWall"));
+        assert_eq!(Some("carom_v2"), data.next("greatWall"));
+        assert_eq!(Some("L2bounce"), data.prev("greatWall"));
+
+        let last_in_chapter = data
+            .chapter_levels(ultra::Chapter::Chapter1)
+            .last()
+            .unwrap()
+            .as_str();
+        let first_in_next = data.chapter_levels(ultra::Chapter::Chapter2)[0].as_str();
+        assert_eq!(Some(first_in_next), data.next(last_in_chapter));
+        assert_eq!(Some(last_in_chapter), data.prev(first_in_next));
+
+        let first = data.chapter_levels(ultra::Chapter::Chapter1)[0].as_str();
+        assert_eq!(None, data.prev(first));
+        assert_eq!(None, data.chapter_of("not a level"));
+
+        assert_eq!(Some("greatWall"), data.level_by_name("great wall"));
+        assert_eq!(Some("greatWall"), data.level_by_name("GREATWALL"));
+        assert_eq!(Some("greatWall"), data.level_by_name("Great Wal"));
+        assert_eq!(Some("greatWall"), data.level_by_name("Graet Wall"));
+        assert_eq!(Some("duality_v2"), data.level_by_name("duality_v2"));
+        assert_eq!(None, data.level_by_name("definitely not a level"));
+        assert_eq!(None, data.level_by_name(""));
+    }
+
+    #[test]
+    fn version_data() {
+        for version in [GameVersion::Classic, GameVersion::Ultra] {
+            let data = version.data().unwrap();
+            assert_eq!(version, data.version());
+
+            let levels = chapters_in_order(&data);
+            assert!(!levels.is_empty());
+            for level in &levels {
+                assert!(data.name(level).is_some());
+            }
+        }
+
+        let classic = GameVersion::Classic.data().unwrap();
+        let ultra_chapter = AnyChapter::Ultra(ultra::Chapter::Bonus1);
+        assert!(classic.chapter_levels(ultra_chapter).is_empty());
+        assert!(ultra_chapter.is_bonus());
+        assert_eq!(1, ultra_chapter.number());
+        assert_eq!("Bonus 1", ultra_chapter.label());
+
+        assert_eq!(
+            chapters_in_order(&classic::Data::new().unwrap()),
+            chapters_in_order(&classic)
+        );
+    }
+
+    #[test]
+    fn localized_names() {
+        // Only English tables are shipped, every language falls back to them
+        assert_eq!("Chapter 2", ultra::Chapter::Chapter2.get_name(NameLang::En));
+        assert_eq!("Chapter 2", ultra::Chapter::Chapter2.get_name(NameLang::Fr));
+        assert_eq!("Bonus 3", ultra::Chapter::Bonus3.get_name(NameLang::Jp));
+        assert_eq!(
+            "Chapter 6",
+            AnyChapter::Classic(classic::Chapter::Chapter6).get_name(NameLang::De)
+        );
+
+        let data = GameVersion::Ultra.data().unwrap();
+        for level in data.levels() {
+            assert_eq!(data.name(level), data.get_name(level, NameLang::Ko));
+        }
+        assert_eq!(
+            "Sugar Rush",
+            classic::Level::SugarRush.get_name(NameLang::Es)
+        );
+        assert_eq!(
+            Some(ultra::Level::GreatWall.name()),
+            data.get_name("SP_greatWall", NameLang::Tr)
+        );
+        assert_eq!(None, data.get_name("rollTutorial", NameLang::En));
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    GameVersion, NameLang,
+    data::{GameChapter, GameData, LevelMetadata},
+};
+
+include!(concat!(env!("OUT_DIR"), "/ultra.rs"));
+
+/// A list of all chapters in this version of the game
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Chapter {
+    #[serde(rename = "Chapter 1")]
+    Chapter1,
+    #[serde(rename = "Chapter 2")]
+    Chapter2,
+    #[serde(rename = "Chapter 3")]
+    Chapter3,
+    #[serde(rename = "Chapter 4")]
+    Chapter4,
+    #[serde(rename = "Chapter 5")]
+    Chapter5,
+    #[serde(rename = "Chapter 6")]
+    Chapter6,
+    #[serde(rename = "Bonus 1")]
+    Bonus1,
+    #[serde(rename = "Bonus 2")]
+    Bonus2,
+    #[serde(rename = "Bonus 3")]
+    Bonus3,
+    #[serde(rename = "Bonus 4")]
+    Bonus4,
+}
+
+impl GameChapter for Chapter {
+    const ALL: &'static [Self] = &[
+        Chapter::Chapter1,
+        Chapter::Chapter2,
+        Chapter::Chapter3,
+        Chapter::Chapter4,
+        Chapter::Chapter5,
+        Chapter::Chapter6,
+        Chapter::Bonus1,
+        Chapter::Bonus2,
+        Chapter::Bonus3,
+        Chapter::Bonus4,
+    ];
+
+    fn version(&self) -> GameVersion {
+        GameVersion::Ultra
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Chapter::Chapter1 => "Chapter 1",
+            Chapter::Chapter2 => "Chapter 2",
+            Chapter::Chapter3 => "Chapter 3",
+            Chapter::Chapter4 => "Chapter 4",
+            Chapter::Chapter5 => "Chapter 5",
+            Chapter::Chapter6 => "Chapter 6",
+            Chapter::Bonus1 => "Bonus 1",
+            Chapter::Bonus2 => "Bonus 2",
+            Chapter::Bonus3 => "Bonus 3",
+            Chapter::Bonus4 => "Bonus 4",
+        }
+    }
+
+    fn is_bonus(&self) -> bool {
+        matches!(
+            self,
+            Chapter::Bonus1 | Chapter::Bonus2 | Chapter::Bonus3 | Chapter::Bonus4
+        )
+    }
+
+    fn number(&self) -> u8 {
+        match self {
+            Chapter::Chapter1 => 1,
+            Chapter::Chapter2 => 2,
+            Chapter::Chapter3 => 3,
+            Chapter::Chapter4 => 4,
+            Chapter::Chapter5 => 5,
+            Chapter::Chapter6 => 6,
+            Chapter::Bonus1 => 1,
+            Chapter::Bonus2 => 2,
+            Chapter::Bonus3 => 3,
+            Chapter::Bonus4 => 4,
+        }
+    }
+}
+
+/// Used to generate the rust data from the source files.  
+///
+/// ```
+/// use miu::data::ultra;
+///
+/// let data = ultra::Data::new().unwrap();
+///
+/// println!("levels: {:#?}", data.levels());
+/// ```
+///
+/// The same data is also available without allocating through [`Level`] and [`Chapter::levels`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Data {
+    levels: Vec<String>,
+    names: HashMap<String, String>,
+    chapters: HashMap<Chapter, Vec<String>>,
+}
+
+impl Data {
+    /// Create a new source of data.  
+    ///
+    /// Built from the static [`Level`] tables, so it never fails.
+    /// The `Result` is only kept for compatibility, [`Data::default`] is the same thing.  
+    pub fn new() -> Result<Self, serde_json::Error> {
+        Ok(Self::default())
+    }
+
+    /// A list of all level ids.  
+    ///
+    /// Note that some Ids will have `SP_` at the start, this one **doesn't**
+    pub fn levels(&self) -> &Vec<String> {
+        &self.levels
+    }
+
+    /// A map of `level id` => `human readable name`.  
+    pub fn names(&self) -> &HashMap<String, String> {
+        &self.names
+    }
+
+    /// A map of `chapter` => `list of level ids`
+    pub fn chapters(&self) -> &HashMap<Chapter, Vec<String>> {
+        &self.chapters
+    }
+
+    /// The classic version of a level, like a remake or the original.  
+    ///
+    /// Accepts the level id with or without `SP_`, see [`Level::classic_equivalent`]
+    pub fn classic_equivalent(&self, level: &str) -> Option<crate::data::classic::Level> {
+        Level::from_id(level)?.classic_equivalent()
+    }
+}
+
+impl Default for Data {
+    fn default() -> Self {
+        Self {
+            levels: Level::ALL.iter().map(|l| l.id().to_string()).collect(),
+            names: Level::ALL
+                .iter()
+                .map(|l| (l.id().to_string(), l.name().to_string()))
+                .collect(),
+            chapters: Chapter::ALL
+                .iter()
+                .map(|c| (*c, c.levels().iter().map(|l| l.id().to_string()).collect()))
+                .collect(),
+        }
+    }
+}
+
+impl GameData for Data {
+    type Chapter = Chapter;
+
+    fn version(&self) -> GameVersion {
+        GameVersion::Ultra
+    }
+
+    fn levels(&self) -> &[String] {
+        &self.levels
+    }
+
+    fn names(&self) -> &HashMap<String, String> {
+        &self.names
+    }
+
+    fn metadata(&self, level: &str) -> Option<LevelMetadata> {
+        Level::from_id(level).map(|level| level.metadata())
+    }
+
+    fn get_name(&self, level: &str, lang: NameLang) -> Option<&str> {
+        match Level::from_id(level) {
+            Some(level) => Some(level.get_name(lang)),
+            None => self.name(level),
+        }
+    }
+
+    fn chapter_levels(&self, chapter: Chapter) -> &[String] {
+        self.chapters
+            .get(&chapter)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
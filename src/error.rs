@@ -8,4 +8,96 @@ pub enum MIUError {
     FailedToParseWeekly(serde_json::Error),
     #[error("Failed to parse the scorebucket in weekly data: {0:?}")]
     FailedToParseScorebucket(serde_json::Error),
+    #[error("Unknown map: {0}")]
+    UnknownMap(String),
+    #[error("Invalid replay file name: {0}")]
+    InvalidReplayName(String),
+    #[error("Unknown language: {0}")]
+    UnknownLanguage(String),
+    #[error("Invalid time: {0}")]
+    InvalidTime(String),
+    #[error("Failed to parse line {0} of the archive: {1:?}")]
+    FailedToParseArchive(usize, serde_json::Error),
+    #[error("Failed to read or write the archive: {0:?}")]
+    Io(std::io::Error),
+    #[error("Parse responded with an error ({code:?}): {message}")]
+    Parse {
+        code: ParseErrorCode,
+        message: String,
+    },
+    #[cfg(feature = "client")]
+    #[error("Request to the parse server failed: {0:?}")]
+    Request(reqwest::Error),
+}
+
+/// The error codes Parse responds with, see `Parse.Error` in the Parse SDKs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseErrorCode {
+    /// `1`, something went wrong on the server
+    InternalServerError,
+    /// `100`, the server couldn't be reached
+    ConnectionFailed,
+    /// `101`, the object doesn't exist or the query matched nothing it could return
+    ObjectNotFound,
+    /// `102`, the query is malformed, like a bad `where` or `order`
+    InvalidQuery,
+    /// `103`, the classname is missing or invalid
+    InvalidClassName,
+    /// `105`, a field name is invalid
+    InvalidKeyName,
+    /// `107`, the request body or parameters wasn't valid json
+    InvalidJson,
+    /// `119`, the app id isn't allowed to do this
+    OperationForbidden,
+    /// `124`, the request took too long on the server
+    Timeout,
+    /// `141`, a cloud function failed
+    ScriptFailed,
+    /// `155`, too many requests, back off for a while
+    RateLimited,
+    /// `209`, the session token is invalid or expired
+    InvalidSessionToken,
+    /// Any other code
+    Other(u32),
+}
+
+impl ParseErrorCode {
+    /// Returns the raw code
+    pub fn code(&self) -> u32 {
+        match self {
+            ParseErrorCode::InternalServerError => 1,
+            ParseErrorCode::ConnectionFailed => 100,
+            ParseErrorCode::ObjectNotFound => 101,
+            ParseErrorCode::InvalidQuery => 102,
+            ParseErrorCode::InvalidClassName => 103,
+            ParseErrorCode::InvalidKeyName => 105,
+            ParseErrorCode::InvalidJson => 107,
+            ParseErrorCode::OperationForbidden => 119,
+            ParseErrorCode::Timeout => 124,
+            ParseErrorCode::ScriptFailed => 141,
+            ParseErrorCode::RateLimited => 155,
+            ParseErrorCode::InvalidSessionToken => 209,
+            ParseErrorCode::Other(code) => *code,
+        }
+    }
+}
+
+impl From<u32> for ParseErrorCode {
+    fn from(code: u32) -> Self {
+        match code {
+            1 => ParseErrorCode::InternalServerError,
+            100 => ParseErrorCode::ConnectionFailed,
+            101 => ParseErrorCode::ObjectNotFound,
+            102 => ParseErrorCode::InvalidQuery,
+            103 => ParseErrorCode::InvalidClassName,
+            105 => ParseErrorCode::InvalidKeyName,
+            107 => ParseErrorCode::InvalidJson,
+            119 => ParseErrorCode::OperationForbidden,
+            124 => ParseErrorCode::Timeout,
+            141 => ParseErrorCode::ScriptFailed,
+            155 => ParseErrorCode::RateLimited,
+            209 => ParseErrorCode::InvalidSessionToken,
+            code => ParseErrorCode::Other(code),
+        }
+    }
 }
@@ -0,0 +1,249 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::{Platform, Score, UserId};
+
+/// A score with its place on a [`Leaderboard`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ranked {
+    /// The rank, starting at 1, tied times share the same rank
+    pub rank: usize,
+    /// The best score of the user
+    pub score: Score,
+}
+
+/// A ranked leaderboard, with only the best score of every user
+///
+/// Ties use competition ranking, so two tied first places are followed by third place.
+///
+/// ```
+/// use miu::{Leaderboard, Score};
+///
+/// # fn scores() -> Vec<Score> { Vec::new() }
+/// let leaderboard = Leaderboard::new(scores());
+///
+/// for entry in leaderboard.top(3) {
+///     println!("#{} {} {}", entry.rank, entry.score.username, entry.score.time);
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Leaderboard {
+    entries: Vec<Ranked>,
+}
+
+impl Leaderboard {
+    /// Ranks the scores, keeping the fastest score per user
+    ///
+    /// Users are told apart by [`UserId::profile_key`], so the same raw id on two platforms
+    /// counts as two users. Tied times are ordered by who set them first, `NaN` times go last.
+    pub fn new(scores: impl IntoIterator<Item = Score>) -> Self {
+        let mut best = HashMap::<String, Score>::new();
+        for score in scores {
+            let key = score.user().profile_key();
+            match best.get(&key) {
+                Some(current) if compare(current, &score) != Ordering::Greater => (),
+                _ => {
+                    best.insert(key, score);
+                }
+            }
+        }
+
+        let mut scores = best.into_values().collect::<Vec<Score>>();
+        scores.sort_by(compare);
+        Self::rank(scores)
+    }
+
+    /// Returns a new leaderboard with only the scores from one platform, re-ranked
+    pub fn filter_platform(&self, platform: &Platform) -> Self {
+        Self::rank(
+            self.entries
+                .iter()
+                .filter(|entry| &entry.score.platform == platform)
+                .map(|entry| entry.score.clone())
+                .collect(),
+        )
+    }
+
+    /// All entries, fastest first
+    pub fn entries(&self) -> &[Ranked] {
+        &self.entries
+    }
+
+    /// The amount of users on the leaderboard
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// If the leaderboard has no scores
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the entry of a user
+    pub fn get(&self, user: &UserId) -> Option<&Ranked> {
+        let key = user.profile_key();
+        self.entries
+            .iter()
+            .find(|entry| entry.score.user().profile_key() == key)
+    }
+
+    /// Returns the rank of a user
+    pub fn rank_of(&self, user: &UserId) -> Option<usize> {
+        self.get(user).map(|entry| entry.rank)
+    }
+
+    /// Returns every entry ranked `n` or better
+    ///
+    /// Can be more than `n` entries if there are ties at the end
+    pub fn top(&self, n: usize) -> &[Ranked] {
+        let end = self.entries.partition_point(|entry| entry.rank <= n);
+        &self.entries[..end]
+    }
+
+    /// Returns the percentage of users `time` is faster than, `0.0..=100.0`
+    ///
+    /// `None` if the leaderboard is empty
+    pub fn percentile(&self, time: f32) -> Option<f32> {
+        if self.entries.is_empty() {
+            return None;
+        }
+
+        let slower = self
+            .entries
+            .iter()
+            .filter(|entry| compare_time(time, entry.score.time) == Ordering::Less)
+            .count();
+
+        Some(slower as f32 / self.entries.len() as f32 * 100.0)
+    }
+
+    /// Assigns ranks to already sorted scores
+    fn rank(scores: Vec<Score>) -> Self {
+        let mut entries: Vec<Ranked> = Vec::with_capacity(scores.len());
+        for (index, score) in scores.into_iter().enumerate() {
+            let rank = match entries.last() {
+                Some(last) if compare_time(last.score.time, score.time) == Ordering::Equal => {
+                    last.rank
+                }
+                _ => index + 1,
+            };
+
+            entries.push(Ranked { rank, score });
+        }
+
+        Self { entries }
+    }
+}
+
+impl FromIterator<Score> for Leaderboard {
+    fn from_iter<T: IntoIterator<Item = Score>>(iter: T) -> Self {
+        Self::new(iter)
+    }
+}
+
+/// Faster times first, then whoever set it first
+fn compare(a: &Score, b: &Score) -> Ordering {
+    compare_time(a.time, b.time).then_with(|| a.created_at.cmp(&b.created_at))
+}
+
+/// Faster times first, `NaN` of either sign last
+fn compare_time(a: f32, b: f32) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (false, false) => a.total_cmp(&b),
+        (a_nan, b_nan) => a_nan.cmp(&b_nan),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Leaderboard, Score, UserId, test_util::gen_score};
+
+    fn score(user_id: &str, time: f32, platform: &str) -> Score {
+        let mut score = gen_score(0.0..1.0);
+        score.user_id = user_id.to_string();
+        score.time = time;
+        score.platform = platform.into();
+        score
+    }
+
+    fn user(user_id: &str, platform: &str) -> UserId {
+        UserId::new(platform.into(), user_id)
+    }
+
+    #[test]
+    fn ranking() {
+        let leaderboard = Leaderboard::new(vec![
+            score("a", 12.0, "PC"),
+            score("b", 10.0, "Switch"),
+            score("a", 9.0, "PC"),
+            score("c", 10.0, "PC"),
+            score("d", -f32::NAN, "PC"),
+            score("e", 15.0, "Switch"),
+        ]);
+
+        let ranks = leaderboard
+            .entries()
+            .iter()
+            .map(|entry| (entry.score.user_id.as_str(), entry.rank))
+            .collect::<Vec<(&str, usize)>>();
+        assert_eq!(5, ranks.len());
+        assert_eq!(("a", 1), ranks[0]);
+        assert_eq!(2, ranks[1].1);
+        assert_eq!(2, ranks[2].1);
+        assert_eq!(("e", 4), ranks[3]);
+        assert_eq!(("d", 5), ranks[4]);
+
+        assert_eq!(Some(1), leaderboard.rank_of(&user("a", "PC")));
+        assert_eq!(
+            Some(9.0),
+            leaderboard
+                .get(&user("a", "PC"))
+                .map(|entry| entry.score.time)
+        );
+        assert_eq!(None, leaderboard.rank_of(&user("a", "Switch")));
+        assert_eq!(None, leaderboard.rank_of(&user("f", "PC")));
+
+        assert_eq!(1, leaderboard.top(1).len());
+        assert_eq!(3, leaderboard.top(2).len());
+        assert_eq!(3, leaderboard.top(3).len());
+        assert_eq!(5, leaderboard.top(10).len());
+    }
+
+    #[test]
+    fn platform_and_percentile() {
+        let leaderboard = Leaderboard::new(vec![
+            score("a", 9.0, "PC"),
+            score("b", 10.0, "Switch"),
+            score("c", 11.0, "PC"),
+            score("e", 15.0, "Switch"),
+        ]);
+
+        let switch = leaderboard.filter_platform(&"Switch".into());
+        assert_eq!(2, switch.len());
+        assert_eq!(Some(1), switch.rank_of(&user("b", "Switch")));
+        assert_eq!(Some(2), switch.rank_of(&user("e", "Switch")));
+
+        assert_eq!(Some(100.0), leaderboard.percentile(1.0));
+        assert_eq!(Some(50.0), leaderboard.percentile(10.0));
+        assert_eq!(Some(0.0), leaderboard.percentile(20.0));
+        assert_eq!(None, Leaderboard::default().percentile(1.0));
+    }
+
+    #[test]
+    fn same_id_on_two_platforms() {
+        let leaderboard = Leaderboard::new(vec![
+            score("a", 10.0, "PC"),
+            score("a", 9.0, "Switch"),
+            score("a", 11.0, "PC"),
+        ]);
+
+        assert_eq!(2, leaderboard.len());
+        assert_eq!(Some(1), leaderboard.rank_of(&user("a", "Switch")));
+        assert_eq!(Some(2), leaderboard.rank_of(&user(" a ", "PC")));
+        assert_eq!(
+            Some(10.0),
+            leaderboard
+                .get(&user("a", "PC"))
+                .map(|entry| entry.score.time)
+        );
+    }
+}
@@ -1,12 +1,23 @@
 mod error;
+mod leaderboard;
+mod map_id;
+mod platform;
+mod race_time;
 mod score;
+mod version;
 mod weekly;
 
 #[cfg(test)]
 mod test_util;
 
 pub use error::*;
+pub use leaderboard::*;
+pub use map_id::*;
+pub use platform::*;
+pub use race_time::*;
 pub use score::*;
+pub use version::*;
 pub use weekly::*;
+pub mod archive;
 pub mod data;
 pub mod parse;
@@ -0,0 +1,141 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{MIUError, data::GameData};
+
+/// A map id, accepts ids both with and without the `SP_` prefix
+///
+/// Scores use the leaderboard form `SP_sugarRush` while [`GameData`] uses the level form `sugarRush`.
+///
+/// ```
+/// use miu::{MapId, data::classic};
+///
+/// let data = classic::Data::new().unwrap();
+/// let map = MapId::new("SP_sugarRush");
+///
+/// assert_eq!("sugarRush", map.level_id());
+/// assert_eq!("Sugar Rush", map.resolve(&data).unwrap().name);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(from = "String", into = "String")]
+pub struct MapId(String);
+
+/// A map found in the game data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedMap<'a, C> {
+    /// The level id, without `SP_`
+    pub level_id: &'a str,
+    /// The human readable name
+    pub name: &'a str,
+    /// The chapter the level is in, `None` if it isn't in any chapter
+    pub chapter: Option<C>,
+}
+
+impl MapId {
+    /// The prefix leaderboard map ids have
+    pub const PREFIX: &'static str = "SP_";
+
+    /// Creates a map id from either form
+    pub fn new(id: &str) -> Self {
+        let id = id.trim();
+        Self(id.strip_prefix(Self::PREFIX).unwrap_or(id).to_string())
+    }
+
+    /// The id without `SP_`, as used by [`GameData`]
+    pub fn level_id(&self) -> &str {
+        &self.0
+    }
+
+    /// The id with `SP_`, as used by leaderboards and scores
+    pub fn leaderboard_id(&self) -> String {
+        format!("{}{}", Self::PREFIX, self.0)
+    }
+
+    /// Looks the map up in the game data
+    ///
+    /// Returns [`MIUError::UnknownMap`] if the map isn't in that version of the game
+    pub fn resolve<'a, D: GameData>(
+        &self,
+        data: &'a D,
+    ) -> Result<ResolvedMap<'a, D::Chapter>, MIUError> {
+        let level_id = data
+            .levels()
+            .iter()
+            .find(|level| **level == self.0)
+            .ok_or_else(|| MIUError::UnknownMap(self.leaderboard_id()))?;
+        let name = data
+            .name(level_id)
+            .ok_or_else(|| MIUError::UnknownMap(self.leaderboard_id()))?;
+        let chapter = data.chapter_of(level_id);
+
+        Ok(ResolvedMap {
+            level_id,
+            name,
+            chapter,
+        })
+    }
+}
+
+impl From<&str> for MapId {
+    fn from(id: &str) -> Self {
+        MapId::new(id)
+    }
+}
+
+impl From<String> for MapId {
+    fn from(id: String) -> Self {
+        MapId::new(&id)
+    }
+}
+
+impl From<MapId> for String {
+    fn from(id: MapId) -> Self {
+        id.leaderboard_id()
+    }
+}
+
+impl Display for MapId {
+    /// Formats as the leaderboard id, with `SP_`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", Self::PREFIX, self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        MIUError, MapId,
+        data::{classic, ultra},
+    };
+
+    #[test]
+    fn prefix() {
+        assert_eq!(MapId::new("SP_greatWall"), MapId::new("greatWall"));
+        assert_eq!("greatWall", MapId::new("SP_greatWall").level_id());
+        assert_eq!("SP_greatWall", MapId::new("greatWall").leaderboard_id());
+        assert_eq!(
+            "\"SP_greatWall\"",
+            serde_json::to_string(&MapId::new("greatWall")).unwrap()
+        );
+    }
+
+    #[test]
+    fn resolve() {
+        let classic = classic::Data::new().unwrap();
+        let ultra = ultra::Data::new().unwrap();
+
+        let duality = MapId::new("SP_duality_v2").resolve(&ultra).unwrap();
+        assert_eq!("duality_v2", duality.level_id);
+        assert_eq!("Duality", duality.name);
+        assert_eq!(Some(ultra::Chapter::Chapter2), duality.chapter);
+
+        let roll = MapId::new("rollTutorial").resolve(&classic).unwrap();
+        assert_eq!(Some(classic::Chapter::Chapter1), roll.chapter);
+
+        assert!(matches!(
+            MapId::new("SP_duality_v2").resolve(&classic),
+            Err(MIUError::UnknownMap(id)) if id == "SP_duality_v2"
+        ));
+    }
+}
@@ -0,0 +1,233 @@
+use serde::de::DeserializeOwned;
+
+use crate::{
+    ChallengeLevel, GameVersion, MIUError, Score, WeekState, Weekly,
+    parse::{APP_ID, DOMAIN, Field, Query, Results, WEEKLY_LEVEL_ID, date},
+};
+
+/// The most rows the Parse server returns in a single response
+pub const MAX_LIMIT: u32 = 1000;
+
+/// An async client for the games Parse backend
+///
+/// ```no_run
+/// use miu::parse::{ParseClient, ultra};
+///
+/// # async fn run() -> Result<(), miu::MIUError> {
+/// let client = ParseClient::new();
+/// let scores = client.leaderboard(ultra::LEADERBOARD, "SP_sugarRush").await?;
+///
+/// println!("world record: {:?}", scores.first());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ParseClient {
+    http: reqwest::Client,
+    base_url: String,
+}
+
+impl Default for ParseClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParseClient {
+    /// Creates a client against the games backend at [`DOMAIN`]
+    pub fn new() -> Self {
+        Self::with_base_url(format!("https://{DOMAIN}/parse"))
+    }
+
+    /// Creates a client against any Parse server, like a local mock one
+    ///
+    /// The base url is everything before `/classes/`, e.g. `http://localhost:1337/parse`
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// The base url this client sends its requests to
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Returns the url for a classname on this clients server
+    pub fn class_url(&self, class: &str) -> String {
+        format!("{}/classes/{class}", self.base_url)
+    }
+
+    /// Fetches the scores on a leaderboard for a map, fastest first
+    ///
+    /// `class` is one of the `LEADERBOARD` classnames and `map_id` is the raw id including `SP_`.
+    /// Only the first [`MAX_LIMIT`] scores are returned.
+    pub async fn leaderboard(&self, class: &str, map_id: &str) -> Result<Vec<Score>, MIUError> {
+        let query = Query::new()
+            .equal_to(Field::MapId, map_id)
+            .ascending(Field::Time)
+            .limit(MAX_LIMIT);
+
+        self.find::<Score>(class, &query).await?.into_result()
+    }
+
+    /// Returns how many objects in a classname matches the query
+    pub async fn count(&self, class: &str, query: &Query) -> Result<u64, MIUError> {
+        let query = query.clone().limit(0).count();
+
+        let results = self.find::<serde_json::Value>(class, &query).await?;
+        let count = results.count;
+
+        results.into_result()?;
+        count.ok_or(MIUError::EmptyResults)
+    }
+
+    /// Fetches the weekly challenge metadata
+    pub async fn weekly(&self, version: GameVersion) -> Result<Weekly, MIUError> {
+        let query = Query::new()
+            .equal_to(Field::Other(String::from("LevelID")), WEEKLY_LEVEL_ID)
+            .limit(1);
+
+        let body = self
+            .send(version.weekly_stats(), &query)
+            .await?
+            .text()
+            .await
+            .map_err(MIUError::Request)?;
+
+        Weekly::from_json(&body)
+    }
+
+    /// Fetches the scores for every level in the current or previous challenge, fastest first
+    ///
+    /// Only scores set while the challenge was running are included.
+    /// Only the first [`MAX_LIMIT`] scores per level are returned.
+    pub async fn weekly_leaderboards(
+        &self,
+        version: GameVersion,
+        weekly: &Weekly,
+        state: WeekState,
+    ) -> Result<Vec<(ChallengeLevel, Vec<Score>)>, MIUError> {
+        let challenge = weekly.score_buckets.get(&state);
+
+        let mut leaderboards = Vec::with_capacity(challenge.levels.len());
+        for level in &challenge.levels {
+            let query = Query::new()
+                .equal_to(Field::MapId, level.id.as_str())
+                .greater_than_or_equal(Field::CreatedAt, date(&challenge.start_date))
+                .less_than(Field::CreatedAt, date(&challenge.end_date))
+                .ascending(Field::Time)
+                .limit(MAX_LIMIT);
+
+            let scores = self
+                .find::<Score>(version.weekly(), &query)
+                .await?
+                .into_result()?;
+            leaderboards.push((level.clone(), scores));
+        }
+
+        Ok(leaderboards)
+    }
+
+    /// Sends a query to a classname and returns the raw Parse response
+    pub async fn find<T: DeserializeOwned>(
+        &self,
+        class: &str,
+        query: &Query,
+    ) -> Result<Results<T>, MIUError> {
+        self.send(class, query)
+            .await?
+            .json::<Results<T>>()
+            .await
+            .map_err(MIUError::Request)
+    }
+
+    /// Sends a query to a classname
+    async fn send(&self, class: &str, query: &Query) -> Result<reqwest::Response, MIUError> {
+        let url = match query.to_string().as_str() {
+            "" => self.class_url(class),
+            query => format!("{}?{query}", self.class_url(class)),
+        };
+
+        self.http
+            .get(url)
+            .header("X-Parse-Application-Id", APP_ID)
+            .send()
+            .await
+            .map_err(MIUError::Request)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        GameVersion, WeekState,
+        parse::{Field, ParseClient, Query},
+        test_util::{MockServer, weekly_json},
+    };
+
+    #[tokio::test]
+    async fn leaderboard() {
+        let server = MockServer::respond(
+            r#"{"results":[{"time":12.5,"userID":"UserId1","username":"Username1","mapID":"SP_sugarRush","skinUsed":"swirl","replayVersion":5,"platform":"PC","replay":null,"createdAt":"2024-01-01T00:00:00.000Z","updatedAt":"2024-01-01T00:00:00.000Z","objectId":"abc"}]}"#,
+        );
+        let client = ParseClient::with_base_url(server.url());
+
+        let scores = client
+            .leaderboard("SPLeaderboard", "SP_sugarRush")
+            .await
+            .unwrap();
+        assert_eq!(1, scores.len());
+        assert_eq!("Username1", scores[0].username);
+
+        let request = server.request();
+        assert!(request.starts_with("GET /parse/classes/SPLeaderboard?"));
+        assert!(request.contains("order=time"));
+        assert!(request.contains("limit=1000"));
+        assert!(request.contains("where=%7B%22mapID%22%3A%22SP_sugarRush%22%7D"));
+        assert!(
+            request
+                .to_lowercase()
+                .contains("x-parse-application-id: revector")
+        );
+    }
+
+    #[tokio::test]
+    async fn count() {
+        let server = MockServer::respond(r#"{"results":[],"count":42}"#);
+        let client = ParseClient::with_base_url(server.url());
+
+        let query = Query::new().equal_to(Field::MapId, "SP_sugarRush");
+        assert_eq!(42, client.count("SPLeaderboard", &query).await.unwrap());
+        assert!(server.request().contains("limit=0&count=1"));
+    }
+
+    #[tokio::test]
+    async fn weekly() {
+        let server = MockServer::sequence(&[
+            &weekly_json(),
+            r#"{"results":[]}"#,
+            r#"{"code":155,"error":"Too many requests"}"#,
+        ]);
+        let client = ParseClient::with_base_url(server.url());
+
+        let weekly = client.weekly(GameVersion::Ultra).await.unwrap();
+        assert_eq!("CHALLENGE_DATA", weekly.level_id);
+        assert!(
+            server.requests()[0]
+                .starts_with("GET /parse/classes/ChallengeStats_Mayhem?where=%7B%22LevelID%22%3A%22CHALLENGE_DATA%22%7D&limit=1 ")
+        );
+
+        let error = client
+            .weekly_leaderboards(GameVersion::Ultra, &weekly, WeekState::Current)
+            .await;
+        assert!(error.is_err());
+
+        let requests = server.requests();
+        assert_eq!(3, requests.len());
+        assert!(requests[1].starts_with("GET /parse/classes/ChallengeLB_Mayhem?"));
+        assert!(requests[1].contains("%22mapID%22%3A%22SP_sugarRush%22"));
+        assert!(requests[2].contains("%22mapID%22%3A%22SP_greatWall%22"));
+    }
+}
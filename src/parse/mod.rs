@@ -0,0 +1,126 @@
+#[cfg(feature = "client")]
+mod client;
+#[cfg(feature = "client")]
+mod paginator;
+mod query;
+
+#[cfg(feature = "client")]
+pub use client::{MAX_LIMIT, ParseClient};
+#[cfg(feature = "client")]
+pub use paginator::{Cursor, Paginator};
+pub use query::{Field, Query, date};
+
+use crate::{MIUError, ParseErrorCode};
+
+/// The games backend server URL
+pub const DOMAIN: &str = "www.miubackend.net";
+/// The App Id used as the `X-Parse-Application-Id` header in the parse request
+pub const APP_ID: &str = "ReVector";
+
+/// The `LevelID` of the weekly metadata object in the `WEEKLY_STATS` classes
+pub const WEEKLY_LEVEL_ID: &str = "CHALLENGE_DATA";
+
+/// Parse related constants in the classic version of the game
+pub mod classic {
+    /// Parse classname for the normal leaderboard
+    pub const LEADERBOARD: &str = "SPLeaderboard";
+    /// Parse classname for weekly leaderboards
+    pub const WEEKLY: &str = "ChallengeLB";
+    /// Parse classname for weekly metadata
+    pub const WEEKLY_STATS: &str = "ChallengeStats";
+}
+
+/// Parse related constants in the ultra version of the game
+pub mod ultra {
+    /// Parse classname for the normal leaderboard
+    pub const LEADERBOARD: &str = "SPLeaderboard_Ultra";
+    /// Parse classname for weekly leaderboards
+    pub const WEEKLY: &str = "ChallengeLB_Mayhem";
+    /// Parse classname for weekly metadata
+    pub const WEEKLY_STATS: &str = "ChallengeStats_Mayhem";
+}
+
+/// A Parse response, holds error data or the actual results
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct Results<T> {
+    /// The actual results, always stored in a `Vec`, generic
+    ///
+    /// `None` if the response contains an error
+    pub results: Option<Vec<T>>,
+    /// Holds the error code
+    ///
+    ///`None` if the response was successful
+    pub code: Option<u32>,
+    /// Holds the error message
+    ///
+    ///`None` if the response was successful
+    pub error: Option<String>,
+    /// Holds the total amount of matching objects
+    ///
+    /// Only `Some` if the query asked for a count
+    pub count: Option<u64>,
+}
+
+impl<T> Results<T> {
+    /// Returns the results, or the error Parse responded with
+    ///
+    /// An empty `Vec` is still `Ok`, [`MIUError::EmptyResults`] is only returned
+    /// if the response has neither results nor an error
+    pub fn into_result(self) -> Result<Vec<T>, MIUError> {
+        match (self.results, self.code) {
+            (Some(results), _) => Ok(results),
+            (None, Some(code)) => Err(MIUError::Parse {
+                code: ParseErrorCode::from(code),
+                message: self.error.unwrap_or_default(),
+            }),
+            (None, None) => Err(MIUError::EmptyResults),
+        }
+    }
+}
+
+/// Returns the parse url given a classname
+pub fn format_url(class: &str) -> String {
+    format!("https://{DOMAIN}/parse/classes/{class}")
+}
+
+/// Returns the parse url for a stored file, like a replay
+pub fn file_url(name: &str) -> String {
+    format!(
+        "https://{DOMAIN}/parse/files/{APP_ID}/{}",
+        query::encode(name)
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{MIUError, ParseErrorCode, parse::Results};
+
+    #[test]
+    fn into_result() {
+        let ok = serde_json::from_str::<Results<u32>>(r#"{"results":[1,2]}"#).unwrap();
+        assert_eq!(vec![1, 2], ok.into_result().unwrap());
+
+        let error =
+            serde_json::from_str::<Results<u32>>(r#"{"code":155,"error":"Too many requests"}"#)
+                .unwrap();
+        match error.into_result() {
+            Err(MIUError::Parse { code, message }) => {
+                assert_eq!(ParseErrorCode::RateLimited, code);
+                assert_eq!("Too many requests", message);
+            }
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+
+        let unknown = serde_json::from_str::<Results<u32>>(r#"{"code":4242}"#).unwrap();
+        assert!(matches!(
+            unknown.into_result(),
+            Err(MIUError::Parse {
+                code: ParseErrorCode::Other(4242),
+                ..
+            })
+        ));
+
+        let empty = serde_json::from_str::<Results<u32>>("{}").unwrap();
+        assert!(matches!(empty.into_result(), Err(MIUError::EmptyResults)));
+    }
+}
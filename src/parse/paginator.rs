@@ -0,0 +1,371 @@
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    MIUError, Score,
+    parse::{Field, MAX_LIMIT, ParseClient, Query, date},
+};
+
+/// How far a [`Paginator`] has walked a class
+///
+/// Save it and give it to [`Paginator::resume`] to continue a crawl later
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cursor {
+    /// The `createdAt` of the newest score returned so far
+    pub created_at: Option<DateTime<Utc>>,
+    /// The object ids already returned that share that exact `createdAt`
+    pub seen: Vec<String>,
+    /// How many scores have been returned in total
+    pub fetched: u64,
+    /// If the whole class has been walked
+    pub done: bool,
+}
+
+/// Walks every score matching a query, page by page, oldest first
+///
+/// Pages are keyed on `createdAt` instead of `skip` so they stay correct when
+/// new scores are added during a crawl, and so the [`Cursor`] can be resumed.
+///
+/// ```no_run
+/// use miu::parse::{Field, ParseClient, Query, ultra};
+///
+/// # async fn run() -> Result<(), miu::MIUError> {
+/// let client = ParseClient::new();
+/// let query = Query::new().equal_to(Field::MapId, "SP_sugarRush");
+///
+/// let mut pages = client.paginate(ultra::LEADERBOARD, query);
+/// while let Some(score) = pages.next_score().await? {
+///     println!("{} ({} fetched)", score.username, pages.cursor().fetched);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Paginator<'a> {
+    client: &'a ParseClient,
+    class: String,
+    query: Query,
+    page_size: u32,
+    cursor: Cursor,
+    buffer: VecDeque<Score>,
+    /// If the last fetched page was the final one
+    exhausted: bool,
+}
+
+impl ParseClient {
+    /// Returns a [`Paginator`] over every score in `class` matching `query`
+    ///
+    /// Any order on the query is replaced, as pages are always ordered by `createdAt`.
+    /// If the query selects keys, `objectId` and `createdAt` are always added as the cursor needs them.
+    pub fn paginate(&self, class: &str, query: Query) -> Paginator<'_> {
+        Paginator {
+            client: self,
+            class: class.to_string(),
+            query: query
+                .without_order()
+                .with_keys([Field::ObjectId, Field::CreatedAt]),
+            page_size: MAX_LIMIT,
+            cursor: Cursor::default(),
+            buffer: VecDeque::new(),
+            exhausted: false,
+        }
+    }
+}
+
+impl Paginator<'_> {
+    /// Continues from a previously saved cursor
+    pub fn resume(mut self, cursor: Cursor) -> Self {
+        self.cursor = cursor;
+        self.buffer.clear();
+        self.exhausted = false;
+        self
+    }
+
+    /// Sets how many scores are fetched per request, capped at [`MAX_LIMIT`]
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size.clamp(1, MAX_LIMIT);
+        self
+    }
+
+    /// The current progress
+    ///
+    /// Only scores that have been returned are counted, so it can be saved between any two scores
+    pub fn cursor(&self) -> &Cursor {
+        &self.cursor
+    }
+
+    /// Returns the total amount of scores matching the query, useful for progress bars
+    pub async fn total(&self) -> Result<u64, MIUError> {
+        self.client.count(&self.class, &self.query).await
+    }
+
+    /// Fetches the next page, `None` once the class is exhausted
+    ///
+    /// Scores buffered by [`Paginator::next_score`] are returned first
+    pub async fn next_page(&mut self) -> Result<Option<Vec<Score>>, MIUError> {
+        if self.buffer.is_empty() {
+            let page = self.fetch().await?;
+            self.buffer.extend(page);
+        }
+        if self.buffer.is_empty() {
+            return Ok(None);
+        }
+
+        let page = self.buffer.drain(..).collect::<Vec<Score>>();
+        for score in &page {
+            self.advance(score);
+        }
+        Ok(Some(page))
+    }
+
+    /// Returns the next score, fetching a new page when needed
+    pub async fn next_score(&mut self) -> Result<Option<Score>, MIUError> {
+        if self.buffer.is_empty() {
+            let page = self.fetch().await?;
+            self.buffer.extend(page);
+        }
+
+        let score = self.buffer.pop_front();
+        if let Some(score) = &score {
+            self.advance(score);
+        }
+        Ok(score)
+    }
+
+    /// Fetches every remaining score
+    pub async fn collect_all(mut self) -> Result<Vec<Score>, MIUError> {
+        let mut scores = Vec::new();
+        while let Some(page) = self.next_page().await? {
+            scores.extend(page);
+        }
+
+        Ok(scores)
+    }
+
+    /// Requests the page after the cursor, empty once the class is exhausted
+    async fn fetch(&mut self) -> Result<Vec<Score>, MIUError> {
+        if self.cursor.done {
+            return Ok(Vec::new());
+        }
+
+        // Merged with the caller's own constraints on these fields instead of replacing them
+        let mut query = self.query.clone().ascending(Field::CreatedAt);
+        if let Some(created_at) = self.cursor.created_at {
+            let from = match self
+                .query
+                .operator(Field::CreatedAt, "$gte")
+                .and_then(parse_date)
+            {
+                Some(from) if from > created_at => from,
+                _ => created_at,
+            };
+            query = query.and_constraint(Field::CreatedAt, "$gte", date(&from));
+        }
+        if !self.cursor.seen.is_empty() {
+            let mut excluded = self
+                .query
+                .operator(Field::ObjectId, "$nin")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default();
+            excluded.extend(self.cursor.seen.iter().cloned().map(Value::String));
+            query = query.and_constraint(Field::ObjectId, "$nin", Value::Array(excluded));
+        }
+        let query = query.limit(self.page_size);
+
+        let page = self
+            .client
+            .find::<Score>(&self.class, &query)
+            .await?
+            .into_result()?;
+
+        self.exhausted = page.len() < self.page_size as usize;
+        self.cursor.done = page.is_empty();
+        Ok(page)
+    }
+
+    /// Moves the cursor past a score that has been returned
+    fn advance(&mut self, score: &Score) {
+        if self.cursor.created_at != Some(score.created_at) {
+            self.cursor.created_at = Some(score.created_at);
+            self.cursor.seen.clear();
+        }
+        if let Some(id) = &score.object_id {
+            self.cursor.seen.push(id.clone());
+        }
+
+        self.cursor.fetched += 1;
+        self.cursor.done = self.exhausted && self.buffer.is_empty();
+    }
+}
+
+/// Reads a date made by [`date`]
+fn parse_date(value: &Value) -> Option<DateTime<Utc>> {
+    let iso = value.get("iso")?.as_str()?;
+    DateTime::parse_from_rfc3339(iso)
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{TimeZone, Utc};
+
+    use crate::{
+        parse::{Field, ParseClient, Query, date},
+        test_util::MockServer,
+    };
+
+    fn score(id: &str, created_at: &str) -> String {
+        format!(
+            r#"{{"time":12.5,"userID":"UserId1","username":"Username1","mapID":"SP_sugarRush","skinUsed":"swirl","replayVersion":5,"platform":"PC","replay":null,"createdAt":"{created_at}","updatedAt":"{created_at}","objectId":"{id}"}}"#
+        )
+    }
+
+    #[tokio::test]
+    async fn paginate() {
+        let first = format!(
+            r#"{{"results":[{},{}]}}"#,
+            score("a", "2024-01-01T00:00:00.000Z"),
+            score("b", "2024-01-02T00:00:00.000Z")
+        );
+        let second = format!(
+            r#"{{"results":[{}]}}"#,
+            score("c", "2024-01-02T00:00:00.000Z")
+        );
+        let server = MockServer::sequence(&[&first, &second]);
+        let client = ParseClient::with_base_url(server.url());
+
+        let query = Query::new()
+            .equal_to(Field::MapId, "SP_sugarRush")
+            .descending(Field::Time);
+        let mut pages = client.paginate("SPLeaderboard", query).page_size(2);
+
+        let mut ids = Vec::new();
+        while let Some(score) = pages.next_score().await.unwrap() {
+            ids.push(score.object_id.unwrap());
+        }
+        assert_eq!(vec!["a", "b", "c"], ids);
+
+        let cursor = pages.cursor();
+        assert!(cursor.done);
+        assert_eq!(3, cursor.fetched);
+        assert_eq!(vec!["b", "c"], cursor.seen);
+
+        let requests = server.requests();
+        assert_eq!(2, requests.len());
+        assert!(requests[0].contains("order=createdAt&limit=2 "));
+        assert!(requests[1].contains("%22%24gte%22%3A%7B%22__type%22%3A%22Date%22%2C%22iso%22%3A%222024-01-02T00%3A00%3A00.000Z%22%7D"));
+        assert!(requests[1].contains("%22objectId%22%3A%7B%22%24nin%22%3A%5B%22b%22%5D%7D"));
+    }
+
+    #[tokio::test]
+    async fn resume() {
+        let server = MockServer::respond(r#"{"results":[]}"#);
+        let client = ParseClient::with_base_url(server.url());
+
+        let mut pages = client.paginate("SPLeaderboard", Query::new());
+        assert!(pages.next_page().await.unwrap().is_none());
+        let cursor = pages.cursor().clone();
+        assert!(cursor.done);
+
+        let mut resumed = client
+            .paginate("SPLeaderboard", Query::new())
+            .resume(cursor);
+        assert!(resumed.next_page().await.unwrap().is_none());
+        assert_eq!(1, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn keeps_caller_constraints() {
+        let first = format!(
+            r#"{{"results":[{},{}]}}"#,
+            score("a", "2024-01-02T00:00:00.000Z"),
+            score("b", "2024-01-02T00:00:00.000Z")
+        );
+        let server = MockServer::sequence(&[&first, r#"{"results":[]}"#]);
+        let client = ParseClient::with_base_url(server.url());
+
+        let since = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let query = Query::new()
+            .greater_than_or_equal(Field::CreatedAt, date(&since))
+            .not_contained_in(Field::ObjectId, ["x"])
+            .keys([Field::Time]);
+        let scores = client
+            .paginate("SPLeaderboard", query)
+            .page_size(2)
+            .collect_all()
+            .await
+            .unwrap();
+        assert_eq!(2, scores.len());
+
+        let cursor = Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap();
+        let expected = Query::new()
+            .greater_than_or_equal(Field::CreatedAt, date(&cursor))
+            .not_contained_in(Field::ObjectId, ["x", "a", "b"])
+            .ascending(Field::CreatedAt)
+            .limit(2)
+            .keys([Field::Time, Field::ObjectId, Field::CreatedAt]);
+        let requests = server.requests();
+        assert_eq!(2, requests.len());
+        assert!(requests[0].contains("keys=time%2CobjectId%2CcreatedAt"));
+        assert!(requests[1].contains(&format!("?{expected} ")));
+    }
+
+    #[test]
+    fn exact_match_is_kept() {
+        let client = ParseClient::new();
+        let pages = client.paginate("SPLeaderboard", Query::new().equal_to(Field::ObjectId, "a"));
+        let query =
+            pages
+                .query
+                .clone()
+                .and_constraint(Field::ObjectId, "$nin", serde_json::json!(["a"]));
+        assert_eq!(
+            r#"{"objectId":{"$eq":"a","$nin":["a"]}}"#,
+            query.filter().unwrap().to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn resume_mid_page() {
+        let first = format!(
+            r#"{{"results":[{},{}]}}"#,
+            score("a", "2024-01-01T00:00:00.000Z"),
+            score("b", "2024-01-01T00:00:00.000Z")
+        );
+        let rest = format!(
+            r#"{{"results":[{}]}}"#,
+            score("b", "2024-01-01T00:00:00.000Z")
+        );
+        let server = MockServer::sequence(&[&first, &rest]);
+        let client = ParseClient::with_base_url(server.url());
+
+        let mut pages = client.paginate("SPLeaderboard", Query::new()).page_size(2);
+        let first = pages.next_score().await.unwrap().unwrap();
+        assert_eq!(Some("a"), first.object_id.as_deref());
+
+        // `b` is still buffered, so the cursor only covers `a`
+        let cursor = pages.cursor().clone();
+        assert_eq!(1, cursor.fetched);
+        assert_eq!(vec!["a"], cursor.seen);
+        assert!(!cursor.done);
+
+        let mut resumed = client
+            .paginate("SPLeaderboard", Query::new())
+            .page_size(2)
+            .resume(cursor);
+        let next = resumed.next_score().await.unwrap().unwrap();
+        assert_eq!(Some("b"), next.object_id.as_deref());
+        assert!(resumed.next_score().await.unwrap().is_none());
+        assert_eq!(2, resumed.cursor().fetched);
+        assert!(resumed.cursor().done);
+
+        let requests = server.requests();
+        assert_eq!(2, requests.len());
+        assert!(requests[1].contains("%22objectId%22%3A%7B%22%24nin%22%3A%5B%22a%22%5D%7D"));
+    }
+}
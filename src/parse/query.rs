@@ -0,0 +1,392 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{Map, Value, json};
+
+use crate::parse::format_url;
+
+/// A field on a Parse object, used to filter, order and select in a [`Query`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Field {
+    /// `mapID`, the raw map id including `SP_`
+    MapId,
+    /// `userID`
+    UserId,
+    /// `username`
+    Username,
+    /// `platform`
+    Platform,
+    /// `time`
+    Time,
+    /// `skinUsed`
+    SkinUsed,
+    /// `replayVersion`
+    ReplayVersion,
+    /// `createdAt`
+    CreatedAt,
+    /// `updatedAt`
+    UpdatedAt,
+    /// `objectId`
+    ObjectId,
+    /// Any other field, with its raw Parse name
+    Other(String),
+}
+
+impl Field {
+    /// Returns the name of the field as Parse knows it
+    pub fn as_str(&self) -> &str {
+        match self {
+            Field::MapId => "mapID",
+            Field::UserId => "userID",
+            Field::Username => "username",
+            Field::Platform => "platform",
+            Field::Time => "time",
+            Field::SkinUsed => "skinUsed",
+            Field::ReplayVersion => "replayVersion",
+            Field::CreatedAt => "createdAt",
+            Field::UpdatedAt => "updatedAt",
+            Field::ObjectId => "objectId",
+            Field::Other(name) => name,
+        }
+    }
+}
+
+/// Parse query parameters for a class
+///
+/// ```
+/// use miu::parse::{Field, Query, ultra};
+///
+/// let query = Query::new()
+///     .equal_to(Field::MapId, "SP_sugarRush")
+///     .less_than(Field::Time, 30.0)
+///     .ascending(Field::Time)
+///     .limit(100);
+///
+/// println!("{}", query.url(ultra::LEADERBOARD));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Query {
+    filter: Map<String, Value>,
+    order: Vec<String>,
+    limit: Option<u32>,
+    skip: Option<u32>,
+    keys: Vec<Field>,
+    count: bool,
+}
+
+impl Query {
+    /// Creates an empty query, which matches everything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match objects where `field` is exactly `value`
+    pub fn equal_to(mut self, field: Field, value: impl Into<Value>) -> Self {
+        self.filter.insert(field.as_str().to_string(), value.into());
+        self
+    }
+
+    /// Only match objects where `field` is not `value`
+    pub fn not_equal_to(self, field: Field, value: impl Into<Value>) -> Self {
+        self.constraint(field, "$ne", value.into())
+    }
+
+    /// Only match objects where `field` is above `value`
+    pub fn greater_than(self, field: Field, value: impl Into<Value>) -> Self {
+        self.constraint(field, "$gt", value.into())
+    }
+
+    /// Only match objects where `field` is above or equal to `value`
+    pub fn greater_than_or_equal(self, field: Field, value: impl Into<Value>) -> Self {
+        self.constraint(field, "$gte", value.into())
+    }
+
+    /// Only match objects where `field` is below `value`
+    pub fn less_than(self, field: Field, value: impl Into<Value>) -> Self {
+        self.constraint(field, "$lt", value.into())
+    }
+
+    /// Only match objects where `field` is below or equal to `value`
+    pub fn less_than_or_equal(self, field: Field, value: impl Into<Value>) -> Self {
+        self.constraint(field, "$lte", value.into())
+    }
+
+    /// Only match objects where `field` is any of `values`
+    pub fn contained_in<V: Into<Value>>(
+        self,
+        field: Field,
+        values: impl IntoIterator<Item = V>,
+    ) -> Self {
+        let values = values.into_iter().map(Into::into).collect::<Vec<Value>>();
+        self.constraint(field, "$in", Value::Array(values))
+    }
+
+    /// Only match objects where `field` is none of `values`
+    pub fn not_contained_in<V: Into<Value>>(
+        self,
+        field: Field,
+        values: impl IntoIterator<Item = V>,
+    ) -> Self {
+        let values = values.into_iter().map(Into::into).collect::<Vec<Value>>();
+        self.constraint(field, "$nin", Value::Array(values))
+    }
+
+    /// Only match objects where `field` is set, or isn't set if `exists` is false
+    pub fn exists(self, field: Field, exists: bool) -> Self {
+        self.constraint(field, "$exists", Value::Bool(exists))
+    }
+
+    /// Only match objects where `field` matches a PCRE regex
+    pub fn matches_regex(self, field: Field, pattern: &str) -> Self {
+        self.constraint(field, "$regex", Value::String(pattern.to_string()))
+    }
+
+    /// Sorts the results by `field`, lowest first
+    ///
+    /// Can be called multiple times, earlier calls take priority
+    pub fn ascending(mut self, field: Field) -> Self {
+        self.order.push(field.as_str().to_string());
+        self
+    }
+
+    /// Sorts the results by `field`, highest first
+    ///
+    /// Can be called multiple times, earlier calls take priority
+    pub fn descending(mut self, field: Field) -> Self {
+        self.order.push(format!("-{}", field.as_str()));
+        self
+    }
+
+    /// The max amount of results, Parse caps this at 1000
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Skips the first `skip` results, used for pagination
+    pub fn skip(mut self, skip: u32) -> Self {
+        self.skip = Some(skip);
+        self
+    }
+
+    /// Only return these fields on each result
+    pub fn keys(mut self, keys: impl IntoIterator<Item = Field>) -> Self {
+        self.keys.extend(keys);
+        self
+    }
+
+    /// Also return the total amount of matching objects as `count`
+    ///
+    /// Combine with `limit(0)` to only get the count
+    pub fn count(mut self) -> Self {
+        self.count = true;
+        self
+    }
+
+    /// Returns the `where` filter as json, `None` if nothing is filtered
+    pub fn filter(&self) -> Option<Value> {
+        match self.filter.is_empty() {
+            true => None,
+            false => Some(Value::Object(self.filter.clone())),
+        }
+    }
+
+    /// Returns all query parameters as unencoded `(key, value)` pairs
+    pub fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+
+        if let Some(filter) = self.filter() {
+            pairs.push(("where", filter.to_string()));
+        }
+        if !self.order.is_empty() {
+            pairs.push(("order", self.order.join(",")));
+        }
+        if let Some(limit) = self.limit {
+            pairs.push(("limit", limit.to_string()));
+        }
+        if let Some(skip) = self.skip {
+            pairs.push(("skip", skip.to_string()));
+        }
+        if !self.keys.is_empty() {
+            let keys = self.keys.iter().map(Field::as_str).collect::<Vec<&str>>();
+            pairs.push(("keys", keys.join(",")));
+        }
+        if self.count {
+            pairs.push(("count", String::from("1")));
+        }
+
+        pairs
+    }
+
+    /// Returns the full parse url for a classname with this query
+    pub fn url(&self, class: &str) -> String {
+        match self.to_string().as_str() {
+            "" => format_url(class),
+            query => format!("{}?{query}", format_url(class)),
+        }
+    }
+
+    /// Removes any ordering
+    #[cfg(feature = "client")]
+    pub(crate) fn without_order(mut self) -> Self {
+        self.order.clear();
+        self
+    }
+
+    /// Adds `keys` to the selected keys, unless every key is already returned
+    #[cfg(feature = "client")]
+    pub(crate) fn with_keys(mut self, keys: impl IntoIterator<Item = Field>) -> Self {
+        if !self.keys.is_empty() {
+            for key in keys {
+                if !self.keys.contains(&key) {
+                    self.keys.push(key);
+                }
+            }
+        }
+        self
+    }
+
+    /// Returns the value of an operator like `$gte` on a field
+    #[cfg(feature = "client")]
+    pub(crate) fn operator(&self, field: Field, operator: &str) -> Option<&Value> {
+        match self.filter.get(field.as_str()) {
+            Some(Value::Object(ops)) if !ops.contains_key("__type") => ops.get(operator),
+            _ => None,
+        }
+    }
+
+    /// Adds an operator like [`Query::constraint`], but keeps an exact match on the field as `$eq`
+    #[cfg(feature = "client")]
+    pub(crate) fn and_constraint(mut self, field: Field, operator: &str, value: Value) -> Self {
+        if let Some(entry) = self.filter.get_mut(field.as_str())
+            && !matches!(entry, Value::Object(ops) if !ops.contains_key("__type"))
+        {
+            *entry = json!({ "$eq": entry.take() });
+        }
+
+        self.constraint(field, operator, value)
+    }
+
+    /// Adds an operator like `$gt` to a field, keeping any other operators on it
+    fn constraint(mut self, field: Field, operator: &str, value: Value) -> Self {
+        let entry = self
+            .filter
+            .entry(field.as_str().to_string())
+            .or_insert_with(|| json!({}));
+
+        match entry {
+            Value::Object(ops) if !ops.contains_key("__type") => {
+                ops.insert(operator.to_string(), value);
+            }
+            _ => *entry = json!({ operator: value }),
+        }
+
+        self
+    }
+}
+
+impl Display for Query {
+    /// The url encoded query string, without the leading `?`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pairs = self
+            .pairs()
+            .into_iter()
+            .map(|(key, value)| format!("{key}={}", encode(&value)))
+            .collect::<Vec<String>>();
+
+        write!(f, "{}", pairs.join("&"))
+    }
+}
+
+/// Returns a date in the json form Parse expects when comparing dates
+pub fn date(date: &DateTime<Utc>) -> Value {
+    json!({
+        "__type": "Date",
+        "iso": date.to_rfc3339_opts(SecondsFormat::Millis, true),
+    })
+}
+
+/// Percent encodes everything except unreserved characters
+pub(crate) fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{TimeZone, Utc};
+
+    use crate::parse::{Field, Query, date};
+
+    #[test]
+    fn empty_query() {
+        assert_eq!("", Query::new().to_string());
+        assert_eq!(
+            "https://www.miubackend.net/parse/classes/SPLeaderboard",
+            Query::new().url("SPLeaderboard")
+        );
+    }
+
+    #[test]
+    fn query_string() {
+        let query = Query::new()
+            .equal_to(Field::MapId, "SP_sugarRush")
+            .contained_in(Field::Platform, ["PC", "Switch"])
+            .descending(Field::Time)
+            .ascending(Field::CreatedAt)
+            .limit(100)
+            .skip(200)
+            .keys([Field::Time, Field::Username])
+            .count();
+
+        assert_eq!(
+            vec![
+                (
+                    "where",
+                    String::from(r#"{"mapID":"SP_sugarRush","platform":{"$in":["PC","Switch"]}}"#)
+                ),
+                ("order", String::from("-time,createdAt")),
+                ("limit", String::from("100")),
+                ("skip", String::from("200")),
+                ("keys", String::from("time,username")),
+                ("count", String::from("1")),
+            ],
+            query.pairs()
+        );
+        assert_eq!(
+            "where=%7B%22mapID%22%3A%22SP_sugarRush%22%2C%22platform%22%3A%7B%22%24in%22%3A%5B%22PC%22%2C%22Switch%22%5D%7D%7D&order=-time%2CcreatedAt&limit=100&skip=200&keys=time%2Cusername&count=1",
+            query.to_string()
+        );
+    }
+
+    #[test]
+    fn merged_constraints() {
+        let created = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+        let query = Query::new()
+            .greater_than(Field::Time, 10.0)
+            .less_than_or_equal(Field::Time, 20.0)
+            .matches_regex(Field::Username, "^Ville")
+            .greater_than(Field::CreatedAt, date(&created));
+
+        assert_eq!(
+            r#"{"createdAt":{"$gt":{"__type":"Date","iso":"2024-01-02T03:04:05.000Z"}},"time":{"$gt":10.0,"$lte":20.0},"username":{"$regex":"^Ville"}}"#,
+            query.filter().unwrap().to_string()
+        );
+
+        let replaced = query.equal_to(Field::Time, 15.0);
+        assert_eq!(
+            r#"{"createdAt":{"$gt":{"__type":"Date","iso":"2024-01-02T03:04:05.000Z"}},"time":15.0,"username":{"$regex":"^Ville"}}"#,
+            replaced.filter().unwrap().to_string()
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use std::{convert::Infallible, fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// The platform a score was performed on
+///
+/// Only `PC` is known for sure, any other string is kept as is in [`Platform::Unknown`]
+/// so it serializes back to the same value.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum Platform {
+    /// `PC`
+    Pc,
+    /// Any other platform, with the raw value
+    Unknown(String),
+}
+
+impl Platform {
+    /// Returns the platform as the backend stores it
+    pub fn as_str(&self) -> &str {
+        match self {
+            Platform::Pc => "PC",
+            Platform::Unknown(platform) => platform,
+        }
+    }
+}
+
+impl From<&str> for Platform {
+    fn from(platform: &str) -> Self {
+        match platform {
+            "PC" => Platform::Pc,
+            other => Platform::Unknown(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for Platform {
+    fn from(platform: String) -> Self {
+        Platform::from(platform.as_str())
+    }
+}
+
+impl From<Platform> for String {
+    fn from(platform: Platform) -> Self {
+        platform.as_str().to_string()
+    }
+}
+
+impl FromStr for Platform {
+    type Err = Infallible;
+
+    fn from_str(platform: &str) -> Result<Self, Self::Err> {
+        Ok(Platform::from(platform))
+    }
+}
+
+impl Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A user id together with the platform it belongs to
+///
+/// The raw id is formatted differently depending on platform, on PC it's a 64 bit Steam id.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UserId {
+    /// The platform the id is from
+    pub platform: Platform,
+    /// The raw id, trimmed
+    pub id: String,
+}
+
+impl UserId {
+    /// Creates a user id from the raw `userID` and `platform` of a score
+    pub fn new(platform: Platform, id: &str) -> Self {
+        Self {
+            platform,
+            id: id.trim().to_string(),
+        }
+    }
+
+    /// Returns the Steam id, `None` if the id isn't from PC or isn't numeric
+    pub fn steam_id(&self) -> Option<u64> {
+        match self.platform {
+            Platform::Pc => self.id.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns a key that is unique across every platform, like `steam:76561198000000000`
+    ///
+    /// PC ids use the `steam` namespace, unknown platforms their lowercased name.
+    pub fn profile_key(&self) -> String {
+        let namespace = match &self.platform {
+            Platform::Pc => String::from("steam"),
+            other => other.as_str().to_lowercase(),
+        };
+
+        match self.steam_id() {
+            Some(steam_id) => format!("{namespace}:{steam_id}"),
+            None => format!("{namespace}:{}", self.id),
+        }
+    }
+}
+
+impl Display for UserId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.profile_key())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Platform, UserId};
+
+    #[test]
+    fn platform_round_trip() {
+        for raw in ["PC", "Switch", "PlayStation", "Stadia", "pc"] {
+            let json = format!("\"{raw}\"");
+            let platform = serde_json::from_str::<Platform>(&json).unwrap();
+
+            assert_eq!(raw, platform.to_string());
+            assert_eq!(json, serde_json::to_string(&platform).unwrap());
+        }
+
+        assert_eq!(Platform::Pc, "PC".into());
+        assert_eq!(Platform::Unknown(String::from("Switch")), "Switch".into());
+        assert_eq!(Platform::Unknown(String::from("pc")), "pc".into());
+    }
+
+    #[test]
+    fn profile_key() {
+        let pc = UserId::new(Platform::Pc, " 76561198000000000 ");
+        assert_eq!(Some(76561198000000000), pc.steam_id());
+        assert_eq!("steam:76561198000000000", pc.profile_key());
+
+        let switch = UserId::new("Switch".into(), "76561198000000000");
+        assert_eq!(None, switch.steam_id());
+        assert_eq!("switch:76561198000000000", switch.profile_key());
+
+        let unknown = UserId::new(Platform::Unknown(String::from("Stadia")), "abc");
+        assert_eq!("stadia:abc", unknown.profile_key());
+    }
+}
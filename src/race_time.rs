@@ -0,0 +1,228 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::MIUError;
+
+/// A time in seconds, like [`Score::time`](crate::Score::time)
+///
+/// Formats as `M:SS.mmm` by default, the precision can be changed with `{:.N}`
+/// and other styles are available through [`RaceTime::display`].
+/// Parsing accepts every style, so user submitted times can be compared with scores.
+///
+/// ```
+/// use miu::{RaceTime, TimeStyle};
+///
+/// let time = RaceTime::from_secs(65.242);
+/// assert_eq!("1:05.242", time.to_string());
+/// assert_eq!("1:05.24", format!("{time:.2}"));
+/// assert_eq!("01:05.242", time.display(TimeStyle::InGame).to_string());
+///
+/// assert_eq!(time, "01:05.242".parse().unwrap());
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+#[serde(transparent)]
+pub struct RaceTime(f32);
+
+/// How a [`RaceTime`] is formatted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TimeStyle {
+    /// Only seconds, `65.242`
+    Seconds,
+    /// `M:SS.mmm`, like `1:05.242` or `0:05.242`
+    #[default]
+    Minutes,
+    /// Padded like the game shows times, `MM:SS.mmm`, like `01:05.242` or `00:05.242`
+    InGame,
+    /// `H:MM:SS.mmm`, like `0:01:05.242`
+    Hours,
+}
+
+/// A [`RaceTime`] formatted with a [`TimeStyle`], see [`RaceTime::display`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayTime {
+    time: RaceTime,
+    style: TimeStyle,
+}
+
+impl RaceTime {
+    /// The amount of decimals used when no precision is given
+    pub const DEFAULT_PRECISION: usize = 3;
+
+    /// Creates a time from seconds
+    pub const fn from_secs(secs: f32) -> Self {
+        Self(secs)
+    }
+
+    /// The time in seconds
+    pub const fn secs(&self) -> f32 {
+        self.0
+    }
+
+    /// Formats the time with another style, the precision can still be changed with `{:.N}`
+    pub fn display(&self, style: TimeStyle) -> DisplayTime {
+        DisplayTime { time: *self, style }
+    }
+}
+
+impl From<f32> for RaceTime {
+    fn from(secs: f32) -> Self {
+        Self(secs)
+    }
+}
+
+impl From<RaceTime> for f32 {
+    fn from(time: RaceTime) -> Self {
+        time.0
+    }
+}
+
+impl Display for RaceTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.display(TimeStyle::default()).fmt(f)
+    }
+}
+
+impl Display for DisplayTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let secs = self.time.0;
+        if !secs.is_finite() {
+            return write!(f, "{secs}");
+        }
+
+        // Rounded once up front, so `59.9996` becomes `1:00.000` and not `0:60.000`
+        let precision = f.precision().unwrap_or(RaceTime::DEFAULT_PRECISION).min(9);
+        let scale = 10u64.pow(precision as u32);
+        let units = (f64::from(secs.abs()) * scale as f64).round() as u64;
+        let (whole, fraction) = (units / scale, units % scale);
+
+        if secs.is_sign_negative() && units != 0 {
+            f.write_str("-")?;
+        }
+        match self.style {
+            TimeStyle::Seconds => write!(f, "{whole}")?,
+            TimeStyle::Minutes => write!(f, "{}:{:02}", whole / 60, whole % 60)?,
+            TimeStyle::InGame => write!(f, "{:02}:{:02}", whole / 60, whole % 60)?,
+            TimeStyle::Hours => write!(
+                f,
+                "{}:{:02}:{:02}",
+                whole / 3600,
+                whole / 60 % 60,
+                whole % 60
+            )?,
+        }
+        if precision > 0 {
+            write!(f, ".{fraction:0precision$}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for RaceTime {
+    type Err = MIUError;
+
+    /// Parses `SS.mmm`, `M:SS.mmm` and `H:MM:SS.mmm`, with any amount of padding and decimals
+    fn from_str(time: &str) -> Result<Self, Self::Err> {
+        let invalid = || MIUError::InvalidTime(time.to_string());
+
+        let trimmed = time.trim();
+        let (negative, trimmed) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed),
+        };
+
+        let mut parts = trimmed.split(':').rev();
+        let seconds = parts.next().ok_or_else(invalid)?;
+        if seconds.is_empty()
+            || !seconds.chars().all(|c| c.is_ascii_digit() || c == '.')
+            || seconds.starts_with('.')
+            || seconds.ends_with('.')
+        {
+            return Err(invalid());
+        }
+        let mut total = seconds.parse::<f64>().map_err(|_| invalid())?;
+
+        let fields = parts.collect::<Vec<&str>>();
+        if fields.len() > 2 || (!fields.is_empty() && total >= 60.0) {
+            return Err(invalid());
+        }
+        for (index, field) in fields.iter().enumerate() {
+            if field.is_empty() || !field.chars().all(|c| c.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            let value = field.parse::<u64>().map_err(|_| invalid())?;
+            // Minutes can go past 59 unless there are hours in front of them
+            if index == 0 && fields.len() == 2 && value >= 60 {
+                return Err(invalid());
+            }
+            total += value as f64 * 60f64.powi(index as i32 + 1);
+        }
+
+        Ok(Self(if negative { -total } else { total } as f32))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{RaceTime, TimeStyle, test_util::gen_score};
+
+    #[test]
+    fn display() {
+        let time = RaceTime::from_secs(2_421.592);
+        assert_eq!("40:21.592", time.to_string());
+        assert_eq!("40:21.6", format!("{time:.1}"));
+        assert_eq!("40:22", format!("{time:.0}"));
+        assert_eq!("2421.592", time.display(TimeStyle::Seconds).to_string());
+        assert_eq!("40:21.592", time.display(TimeStyle::InGame).to_string());
+        assert_eq!("0:40:21.592", time.display(TimeStyle::Hours).to_string());
+        assert_eq!(
+            "1:00:00.00",
+            format!(
+                "{:.2}",
+                RaceTime::from_secs(3_599.999).display(TimeStyle::Hours)
+            )
+        );
+
+        let short = RaceTime::from_secs(5.2424);
+        assert_eq!("0:05.242", short.to_string());
+        assert_eq!("00:05.242", short.display(TimeStyle::InGame).to_string());
+        assert_eq!(
+            "5.24240",
+            format!("{:.5}", short.display(TimeStyle::Seconds))
+        );
+        assert_eq!("1:00.000", RaceTime::from_secs(59.9996).to_string());
+        assert_eq!("-0:01.500", RaceTime::from_secs(-1.5).to_string());
+        assert_eq!("NaN", RaceTime::from_secs(f32::NAN).to_string());
+    }
+
+    #[test]
+    fn parse() {
+        for (text, secs) in [
+            ("5.242", 5.242),
+            ("05", 5.0),
+            ("0:05.242", 5.242),
+            ("1:05.242", 65.242),
+            ("01:05.242", 65.242),
+            ("40:21.592", 2_421.592),
+            ("75:00", 4_500.0),
+            ("1:00:00.5", 3_600.5),
+            (" 0:01:05.242 ", 65.242),
+            ("-1.5", -1.5),
+        ] {
+            assert_eq!(RaceTime::from_secs(secs), text.parse().unwrap(), "{text}");
+        }
+
+        for text in [
+            "", "abc", "1:60", "1:75:00", "1::05", ":05", "1:2:3:4", "5.", "1e3", "+5",
+        ] {
+            assert!(text.parse::<RaceTime>().is_err(), "{text}");
+        }
+
+        let mut score = gen_score(0.0..1.0);
+        score.time = 125.242966;
+        let time = score.race_time();
+        assert_eq!("2:05.243", time.to_string());
+        assert_eq!(time, format!("{time:.6}").parse().unwrap());
+    }
+}
@@ -1,8 +1,10 @@
-use std::time::Duration;
+use std::{fmt::Display, time::Duration};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::{MIUError, MapId, Platform, RaceTime, UserId, parse::file_url};
+
 /// A Score struct
 ///
 /// Common across normal leaderboards and weekly challenges leaderboards
@@ -12,6 +14,8 @@ pub struct Score {
     pub time: f32,
 
     /// The user id, is differently formatted depending on platform
+    ///
+    /// Use [`Score::user`] for a typed id
     #[serde(rename = "userID")]
     pub user_id: String,
 
@@ -19,6 +23,8 @@ pub struct Score {
     pub username: String,
 
     /// The raw mapid, includes `SP_###`
+    ///
+    /// Use [`Score::map`] for a typed id
     #[serde(rename = "mapID")]
     pub map_id: String,
 
@@ -31,7 +37,7 @@ pub struct Score {
     pub replay_version: u32,
 
     /// The platform the score was performed on
-    pub platform: String,
+    pub platform: Platform,
     /// The replay struct, contains further replay information
     pub replay: Option<Replay>,
 
@@ -58,11 +64,94 @@ pub struct Replay {
     pub name: String,
     /// The url for the replay
     ///
-    /// Kinda odd one since its a local host, use the name and replay module instead of this
+    /// Kinda odd one since its a local host, use [`Score::replay_url`] instead of this
     pub url: String,
 }
 
+/// The parts of a replay file name, `REPLAY_<userid>_<username>.replay`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReplayName {
+    /// The random prefix Parse adds to stored files, `None` for canonical names
+    pub file_prefix: Option<String>,
+    /// The user id of whoever set the score
+    pub user_id: String,
+    /// The username of whoever set the score
+    pub username: String,
+}
+
+impl Replay {
+    /// Splits the file name into its parts
+    pub fn parse_name(&self) -> Result<ReplayName, MIUError> {
+        self.name.parse()
+    }
+
+    /// The url the replay can be downloaded from
+    pub(crate) fn download_url(&self) -> String {
+        file_url(&self.name)
+    }
+}
+
+impl std::str::FromStr for ReplayName {
+    type Err = MIUError;
+
+    /// Parses both canonical names and names with the Parse file prefix, `<prefix>_REPLAY_...`
+    ///
+    /// The user id can't contain `_`, anything after it is the username
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let invalid = || MIUError::InvalidReplayName(name.to_string());
+
+        let stem = name.strip_suffix(".replay").ok_or_else(invalid)?;
+        let (file_prefix, rest) = match stem.strip_prefix("REPLAY_") {
+            Some(rest) => (None, rest),
+            None => {
+                let (prefix, rest) = stem.split_once("_REPLAY_").ok_or_else(invalid)?;
+                (Some(prefix.to_string()), rest)
+            }
+        };
+
+        match rest.split_once('_') {
+            Some((user_id, username)) if !user_id.is_empty() && !username.is_empty() => Ok(Self {
+                file_prefix,
+                user_id: user_id.to_string(),
+                username: username.to_string(),
+            }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl Display for ReplayName {
+    /// Formats back into the file name
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(prefix) = &self.file_prefix {
+            write!(f, "{prefix}_")?;
+        }
+
+        write!(f, "REPLAY_{}_{}.replay", self.user_id, self.username)
+    }
+}
+
 impl Score {
+    /// Returns the typed map id
+    pub fn map(&self) -> MapId {
+        MapId::new(&self.map_id)
+    }
+
+    /// Returns the user id together with its platform
+    pub fn user(&self) -> UserId {
+        UserId::new(self.platform.clone(), &self.user_id)
+    }
+
+    /// Returns the url the scores replay can be downloaded from, `None` if it has no replay
+    pub fn replay_url(&self) -> Option<String> {
+        self.replay.as_ref().map(Replay::download_url)
+    }
+
+    /// Returns the time as a [`RaceTime`], which can be formatted in other styles
+    pub fn race_time(&self) -> RaceTime {
+        RaceTime::from_secs(self.time)
+    }
+
     /// Returns a formatted time
     ///
     /// In the format of: `MM:SS:MS` only if the time is above a minute,
@@ -89,9 +178,10 @@ impl Score {
 
 #[cfg(test)]
 mod test {
-    use crate::test_util::gen_score;
+    use crate::{ReplayName, test_util::gen_score};
 
     #[test]
+    #[allow(clippy::excessive_precision)]
     fn test_format_time() {
         let mut score_1 = gen_score(0.0..1.0);
         score_1.time = 1.0;
@@ -107,4 +197,45 @@ mod test {
         assert_eq!("02:05.242966", score_3.format_time());
         assert_eq!("40:21.592041", score_4.format_time());
     }
+
+    #[test]
+    fn replay_name() {
+        let score = gen_score(0.0..1.0);
+        let name = score.replay.as_ref().unwrap().parse_name().unwrap();
+        assert_eq!(None, name.file_prefix);
+        assert_eq!("USERID", name.user_id);
+        assert_eq!("USERNAME", name.username);
+        assert_eq!("REPLAY_USERID_USERNAME.replay", name.to_string());
+
+        let stored = "8f3c_REPLAY_76561198000000000_Ville_Olof.replay"
+            .parse::<ReplayName>()
+            .unwrap();
+        assert_eq!(Some(String::from("8f3c")), stored.file_prefix);
+        assert_eq!("76561198000000000", stored.user_id);
+        assert_eq!("Ville_Olof", stored.username);
+
+        assert!("REPLAY_USERID.replay".parse::<ReplayName>().is_err());
+        assert!("REPLAY_USERID_USERNAME.png".parse::<ReplayName>().is_err());
+    }
+
+    #[test]
+    fn replay_url() {
+        let mut score = gen_score(0.0..1.0);
+        assert_eq!(
+            Some("https://www.miubackend.net/parse/files/ReVector/REPLAY_USERID_USERNAME.replay"),
+            score.replay_url().as_deref()
+        );
+
+        score.replay.as_mut().unwrap().name =
+            String::from("8f3c_REPLAY_76561198000000000_Ville_Olof.replay");
+        assert_eq!(
+            Some(
+                "https://www.miubackend.net/parse/files/ReVector/8f3c_REPLAY_76561198000000000_Ville_Olof.replay"
+            ),
+            score.replay_url().as_deref()
+        );
+
+        score.replay = None;
+        assert_eq!(None, score.replay_url());
+    }
 }
@@ -8,7 +8,7 @@ use crate::{Replay, Score};
 
 /// Generates a fake score
 pub fn gen_score(time_range: Range<f32>) -> Score {
-    fn get_random_elem(mut rng: &mut ThreadRng, vec: &Vec<String>) -> String {
+    fn get_random_elem(mut rng: &mut ThreadRng, vec: &[String]) -> String {
         vec.choose(&mut rng).unwrap().to_owned()
     }
 
@@ -35,3 +35,130 @@ pub fn gen_score(time_range: Range<f32>) -> Score {
         object_id: None,
     }
 }
+
+/// A fake `ChallengeStats` response, with `ScoreBuckets` as a json string like the real one
+pub fn weekly_json() -> String {
+    let score_buckets = serde_json::json!({
+        "current": {
+            "chapterSet": "weekly",
+            "challengeID": "challenge_2",
+            "levels": [
+                {
+                    "name": "Sugar Crash",
+                    "id": "SP_sugarRush",
+                    "physicsmod": { "gravity": 0.5, "reverse": true }
+                },
+                {
+                    "name": "Great Fall",
+                    "id": "SP_greatWall",
+                    "physicsmod": { "nogems": true, "airjumps": 2 }
+                }
+            ],
+            "name": { "en": "Low Gravity", "fr": "Faible Gravité" },
+            "startDate": "2024-01-08T00:00:00Z",
+            "endDate": "2024-01-15T00:00:00Z"
+        },
+        "previous": {
+            "chapterSet": "weekly",
+            "challengeID": "challenge_1",
+            "levels": [
+                {
+                    "name": "Duality Reversed",
+                    "id": "SP_duality_v2",
+                    "physicsmod": { "reverse": true, "notimetravel": true }
+                }
+            ],
+            "name": { "en": "Backwards" },
+            "startDate": "2024-01-01T00:00:00Z",
+            "endDate": "2024-01-08T00:00:00Z"
+        },
+        "sheetID": 1,
+        "curID": 2,
+        "level": "CHALLENGE_DATA"
+    });
+
+    serde_json::json!({
+        "results": [{
+            "objectId": "weekly",
+            "LevelID": "CHALLENGE_DATA",
+            "createdAt": "2024-01-01T00:00:00.000Z",
+            "updatedAt": "2024-01-08T00:00:00.000Z",
+            "ScoreBuckets": score_buckets.to_string()
+        }]
+    })
+    .to_string()
+}
+
+/// A tiny local Parse server, answers requests with canned json bodies
+#[cfg(feature = "client")]
+pub struct MockServer {
+    addr: std::net::SocketAddr,
+    requests: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+}
+
+#[cfg(feature = "client")]
+impl MockServer {
+    /// Starts the server on a random local port, answering every request with `body`
+    pub fn respond(body: &str) -> Self {
+        Self::sequence(&[body])
+    }
+
+    /// Starts the server on a random local port
+    ///
+    /// The n:th request gets the n:th body, the last body is repeated after that
+    pub fn sequence(bodies: &[&str]) -> Self {
+        use std::io::{BufRead, BufReader, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let bodies = bodies
+            .iter()
+            .map(|b| b.to_string())
+            .collect::<Vec<String>>();
+        let received = requests.clone();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut request = String::new();
+                let mut reader = BufReader::new(&stream);
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                        break;
+                    }
+                    request.push_str(&line);
+                }
+                let body = {
+                    let mut received = received.lock().unwrap();
+                    received.push(request);
+                    bodies[(received.len() - 1).min(bodies.len() - 1)].clone()
+                };
+
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+            }
+        });
+
+        Self { addr, requests }
+    }
+
+    /// The base url to give to the client
+    pub fn url(&self) -> String {
+        format!("http://{}/parse", self.addr)
+    }
+
+    /// The first raw request the server received, request line and headers
+    pub fn request(&self) -> String {
+        self.requests.lock().unwrap()[0].clone()
+    }
+
+    /// Every raw request the server received, in order
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::{self, VersionData},
+    parse::{classic, ultra},
+};
+
+/// The two versions of the game
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameVersion {
+    /// Marble It Up!
+    Classic,
+    /// Marble It Up! Ultra
+    Ultra,
+}
+
+impl GameVersion {
+    /// Parse classname for the normal leaderboard
+    pub fn leaderboard(&self) -> &'static str {
+        match self {
+            GameVersion::Classic => classic::LEADERBOARD,
+            GameVersion::Ultra => ultra::LEADERBOARD,
+        }
+    }
+
+    /// Parse classname for weekly leaderboards
+    pub fn weekly(&self) -> &'static str {
+        match self {
+            GameVersion::Classic => classic::WEEKLY,
+            GameVersion::Ultra => ultra::WEEKLY,
+        }
+    }
+
+    /// Parse classname for weekly metadata
+    pub fn weekly_stats(&self) -> &'static str {
+        match self {
+            GameVersion::Classic => classic::WEEKLY_STATS,
+            GameVersion::Ultra => ultra::WEEKLY_STATS,
+        }
+    }
+
+    /// Loads the game data for this version
+    ///
+    /// Use [`data::classic::Data`] or [`data::ultra::Data`] directly when the version is known up front
+    pub fn data(&self) -> Result<VersionData, serde_json::Error> {
+        Ok(match self {
+            GameVersion::Classic => VersionData::Classic(data::classic::Data::new()?),
+            GameVersion::Ultra => VersionData::Ultra(data::ultra::Data::new()?),
+        })
+    }
+}
@@ -2,9 +2,8 @@ use std::collections::HashMap;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use serde_with::{EnumMap, serde_as};
 
-use crate::{NameLang, PhysicsMod};
+use crate::{MapId, ModCategory, NameLang, PhysicsMod, PhysicsModSet};
 
 /// A challenge, contains levels, name translation, start and end dates
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -21,7 +20,7 @@ pub struct Challenge {
     pub levels: Vec<ChallengeLevel>,
     /// Name translation, where key is the language and value is the corresponding name
     ///
-    /// Use `Challenge::get_name(&self, lang: NameLang)` instead to get the translated name
+    /// Use [`Challenge::get_name`] or [`Challenge::get_name_or_fallback`] instead to get the translated name
     pub name: HashMap<String, String>,
 
     /// The start date of the challenge, in Utc
@@ -34,7 +33,6 @@ pub struct Challenge {
 }
 
 /// A level in a challenge
-#[serde_as]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ChallengeLevel {
     /// The fancy level title
@@ -42,17 +40,223 @@ pub struct ChallengeLevel {
     /// The map id, *probably contains the "SP_###"*
     pub id: String,
     /// All physics mod for the level
-    #[serde_as(as = "EnumMap")]
-    pub physicsmod: Vec<PhysicsMod>,
+    ///
+    /// Mods this version of the crate doesn't know are kept as [`PhysicsMod::Unknown`]
+    pub physicsmod: PhysicsModSet,
+}
+
+impl ChallengeLevel {
+    /// Returns the typed map id
+    pub fn map(&self) -> MapId {
+        MapId::new(&self.id)
+    }
+
+    /// Returns every mod that couldn't be parsed, see [`PhysicsMod::warning`]
+    pub fn unknown_mods(&self) -> Vec<&PhysicsMod> {
+        self.physicsmod.unknown().collect()
+    }
+
+    /// The categories of the mods on this level, without duplicates, in the order of [`ModCategory::ALL`]
+    ///
+    /// Mods set to their default are left out, see [`PhysicsMod::is_neutral`]
+    pub fn categories(&self) -> Vec<ModCategory> {
+        ModCategory::ALL
+            .iter()
+            .filter(|category| {
+                self.physicsmod.iter().any(|physics_mod| {
+                    !physics_mod.is_neutral() && physics_mod.category() == **category
+                })
+            })
+            .copied()
+            .collect()
+    }
+
+    /// A rough guess of how much harder the mods make the level, `0.0` for an unmodified level
+    ///
+    /// Every multiplier adds more the further it is from its [`PhysicsMod::DEFAULTS`] value,
+    /// in either direction. Reversing, removing time travels and boomerangs add a fixed amount,
+    /// and some combinations, like reversed without time travels, add extra.
+    /// No gems and air jumps make a level easier, so the estimate is negative when those win.
+    /// Only meant to compare challenges with each other, the numbers have no unit.
+    pub fn difficulty_estimate(&self) -> f32 {
+        let mods = &self.physicsmod;
+
+        // How far a multiplier is from its default, half and double count the same
+        let deviation = |physics_mod: &PhysicsMod| {
+            let value = physics_mod.as_f32()?;
+            let default = PhysicsMod::default_for(physics_mod.key())?.as_f32()?;
+            Some(match value > 0.0 {
+                true => (value / default).ln().abs().min(3.0),
+                false => 3.0,
+            })
+        };
+        let weight = |physics_mod: &PhysicsMod| match physics_mod {
+            PhysicsMod::Gravity(_) | PhysicsMod::FrictionMult(_) => 1.5,
+            PhysicsMod::BlastJumpMult(_)
+            | PhysicsMod::BlastPushMult(_)
+            | PhysicsMod::BlastRangeMult(_)
+            | PhysicsMod::BlastCooldownMult(_) => 0.5,
+            PhysicsMod::BlastX(_)
+            | PhysicsMod::BlastY(_)
+            | PhysicsMod::ImpactX(_)
+            | PhysicsMod::ImpactY(_)
+            | PhysicsMod::MegaForce(_) => 0.0,
+            _ => 1.0,
+        };
+
+        let mut score = mods
+            .iter()
+            .filter_map(|physics_mod| Some(weight(physics_mod) * deviation(physics_mod)?))
+            .sum::<f32>();
+
+        let flags = [
+            (mods.reverse(), 1.0),
+            (mods.no_time_travel(), 1.0),
+            (mods.boomerang(), 1.5),
+            (mods.checkpoint_gems(), 0.5),
+            (mods.trophy_gem(), 0.5),
+            (mods.no_powerups(), 0.5),
+            (mods.reverse() && mods.no_time_travel(), 1.0),
+            (mods.reverse() && mods.friction_mult() < 1.0, 0.5),
+            (mods.no_gems(), -0.5),
+        ];
+        score += flags
+            .iter()
+            .filter(|(set, _)| *set)
+            .map(|(_, weight)| weight)
+            .sum::<f32>();
+
+        let default_jumps = PhysicsMod::default_for("airjumps")
+            .and_then(PhysicsMod::as_i32)
+            .unwrap_or_default();
+        score -= 0.25 * (mods.air_jumps() - default_jumps).max(0) as f32;
+
+        score
+    }
 }
 
 impl Challenge {
-    /// Returns a translated name of the challenge
-    pub fn get_name(&self, lang: NameLang) -> String {
-        if let Some(name) = self.name.get(&lang.to_string()) {
-            return name.to_owned();
-        }
+    /// Returns a translated name of the challenge, `None` if there is no translation
+    ///
+    /// Empty names count as missing.
+    pub fn get_name(&self, lang: NameLang) -> Option<&str> {
+        self.name
+            .get(lang.as_str())
+            .or_else(|| self.name.get(lang.bcp47()))
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty())
+    }
+
+    /// Returns the name in the first language that has one, trying `lang`, then `fallbacks` in order,
+    /// then English
+    ///
+    /// `None` only if the challenge has no name in any of those languages
+    pub fn get_name_or_fallback(&self, lang: NameLang, fallbacks: &[NameLang]) -> Option<&str> {
+        std::iter::once(lang)
+            .chain(fallbacks.iter().copied())
+            .chain(std::iter::once(NameLang::En))
+            .find_map(|lang| self.get_name(lang))
+    }
+
+    /// The categories of the mods on every level, without duplicates, in the order of [`ModCategory::ALL`]
+    pub fn categories(&self) -> Vec<ModCategory> {
+        ModCategory::ALL
+            .iter()
+            .filter(|category| {
+                self.levels
+                    .iter()
+                    .any(|level| level.categories().contains(category))
+            })
+            .copied()
+            .collect()
+    }
+
+    /// Returns every language the challenge has no name in
+    pub fn missing_languages(&self) -> Vec<NameLang> {
+        NameLang::all()
+            .filter(|lang| self.get_name(*lang).is_none())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        ModCategory, NameLang, PhysicsMod, PhysicsModSet, WeekState, Weekly, test_util::weekly_json,
+    };
+
+    #[test]
+    fn names() {
+        let weekly = Weekly::from_json(&weekly_json()).unwrap();
+        let current = weekly.score_buckets.get(&WeekState::Current);
+
+        assert_eq!(Some("Faible Gravité"), current.get_name(NameLang::Fr));
+        assert_eq!(None, current.get_name(NameLang::De));
+        assert_eq!(
+            Some("Faible Gravité"),
+            current.get_name_or_fallback(NameLang::De, &[NameLang::Fr])
+        );
+        assert_eq!(
+            Some("Low Gravity"),
+            current.get_name_or_fallback(NameLang::De, &[NameLang::Es])
+        );
+
+        let missing = current.missing_languages();
+        assert_eq!(12, missing.len());
+        assert!(!missing.contains(&NameLang::En));
+        assert!(!missing.contains(&NameLang::Fr));
+
+        let mut previous = weekly.score_buckets.get(&WeekState::Previous).clone();
+        previous.name.clear();
+        assert_eq!(None, previous.get_name_or_fallback(NameLang::Fr, &[]));
+        assert_eq!(NameLang::all().count(), previous.missing_languages().len());
+    }
+
+    #[test]
+    fn tags_and_difficulty() {
+        let weekly = Weekly::from_json(&weekly_json()).unwrap();
+        let current = weekly.score_buckets.get(&WeekState::Current);
+        let previous = weekly.score_buckets.get(&WeekState::Previous);
+
+        let sugar_rush = &current.levels[0];
+        assert_eq!(
+            vec![ModCategory::Movement, ModCategory::LevelLayout],
+            sugar_rush.categories()
+        );
+        assert_eq!(
+            vec![ModCategory::Movement, ModCategory::LevelLayout],
+            current.levels[1].categories()
+        );
+        assert_eq!("Level Layout", ModCategory::LevelLayout.to_string());
+        assert_eq!(
+            vec![ModCategory::Movement, ModCategory::LevelLayout],
+            current.categories()
+        );
+        assert_eq!(vec![ModCategory::LevelLayout], previous.categories());
+
+        let mut unmodified = sugar_rush.clone();
+        unmodified.physicsmod = PhysicsModSet::new();
+        assert_eq!(0.0, unmodified.difficulty_estimate());
+
+        // Reversed without time travels is harder than reversed with low gravity
+        let duality = &previous.levels[0];
+        assert!(sugar_rush.difficulty_estimate() > 0.0);
+        assert!(duality.difficulty_estimate() > sugar_rush.difficulty_estimate());
+
+        // No gems with air jumps is easier than an unmodified level, not the same
+        let easier = &current.levels[1];
+        assert!(easier.difficulty_estimate() < unmodified.difficulty_estimate());
+        assert_eq!(-1.0, easier.difficulty_estimate());
+
+        // Neutral values don't count
+        let mut neutral = unmodified.clone();
+        neutral.physicsmod.insert(PhysicsMod::Gravity(1.0));
+        neutral.physicsmod.insert(PhysicsMod::Boomerang(false));
+        assert_eq!(0.0, neutral.difficulty_estimate());
+        assert!(neutral.categories().is_empty());
 
-        String::from("Unknown")
+        let mut neutral_challenge = previous.clone();
+        neutral_challenge.levels = vec![neutral];
+        assert!(neutral_challenge.categories().is_empty());
     }
 }
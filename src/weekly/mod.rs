@@ -1,11 +1,17 @@
 pub(crate) mod challenge;
+pub(crate) mod mod_category;
 pub(crate) mod name_lang;
 pub(crate) mod physics_mod;
+pub(crate) mod physics_mod_format;
+pub(crate) mod physics_mod_set;
 pub(crate) mod scorebucket;
 
 pub use challenge::{Challenge, ChallengeLevel};
+pub use mod_category::ModCategory;
 pub use name_lang::NameLang;
 pub use physics_mod::PhysicsMod;
+pub use physics_mod_format::{LabelLang, ModFormat, ValueStyle};
+pub use physics_mod_set::{ModChange, PhysicsModSet};
 pub use scorebucket::ScoreBucket;
 
 use chrono::{DateTime, Utc};
@@ -65,19 +71,12 @@ struct MidWeekly {
 impl Weekly {
     /// Since some internal fields are json within already parsed json, this handles two json's.  
     pub fn from_json(json: &str) -> Result<Weekly, MIUError> {
-        let mid = match serde_json::from_str::<Results<MidWeekly>>(json) {
-            Ok(mid) => match mid.results {
-                Some(results) => {
-                    if results.is_empty() {
-                        return Err(MIUError::EmptyResults);
-                    }
-
-                    results.first().unwrap().clone()
-                }
-                None => return Err(MIUError::EmptyResults),
-            },
-            Err(err) => return Err(MIUError::FailedToParseWeekly(err)),
-        };
+        let mid = serde_json::from_str::<Results<MidWeekly>>(json)
+            .map_err(MIUError::FailedToParseWeekly)?
+            .into_result()?
+            .into_iter()
+            .next()
+            .ok_or(MIUError::EmptyResults)?;
 
         let score_buckets = match serde_json::from_str::<ScoreBucket>(&mid.score_buckets) {
             Ok(s_bucket) => s_bucket,
@@ -92,4 +91,60 @@ impl Weekly {
             score_buckets,
         })
     }
+
+    /// Returns a warning for every physics mod that couldn't be parsed
+    ///
+    /// Parsing doesn't fail on unknown mods, so new mods added to the game can be spotted here
+    pub fn warnings(&self) -> Vec<String> {
+        [&self.score_buckets.current, &self.score_buckets.previous]
+            .into_iter()
+            .flat_map(|challenge| {
+                challenge.levels.iter().flat_map(move |level| {
+                    level.physicsmod.iter().filter_map(move |physics_mod| {
+                        let warning = physics_mod.warning()?;
+                        Some(format!(
+                            "{} {}: {warning}",
+                            challenge.challenge_id, level.id
+                        ))
+                    })
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{PhysicsMod, WeekState, Weekly, test_util::weekly_json};
+
+    #[test]
+    fn from_json() {
+        let weekly = Weekly::from_json(&weekly_json()).unwrap();
+
+        let current = weekly.score_buckets.get(&WeekState::Current);
+        assert_eq!("challenge_2", current.challenge_id);
+        assert_eq!(2, current.levels.len());
+        assert!(
+            current.levels[0]
+                .physicsmod
+                .contains(&PhysicsMod::Gravity(0.5))
+        );
+
+        let previous = weekly.score_buckets.get(&WeekState::Previous);
+        assert_eq!("challenge_1", previous.challenge_id);
+        assert!(weekly.warnings().is_empty());
+    }
+
+    #[test]
+    fn unknown_mods() {
+        let json = weekly_json().replace(r#"\"nogems\":true"#, r#"\"nogems\":1.5"#);
+        let weekly = Weekly::from_json(&json).unwrap();
+
+        assert_eq!(
+            vec![String::from(
+                "challenge_2 SP_greatWall: physics mod `nogems` has an unexpected value `1.5`"
+            )],
+            weekly.warnings()
+        );
+    }
 }
@@ -0,0 +1,96 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::PhysicsMod;
+
+/// What part of the game a [`PhysicsMod`] changes, used to tag challenges
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ModCategory {
+    /// How the marble moves, like gravity, friction and jumps
+    Movement,
+    /// The blast ability
+    Blast,
+    /// Gems, time travels, the goal and the direction of the level
+    LevelLayout,
+    /// Which powerups are available
+    Powerups,
+    /// Sounds and shadows, doesn't change gameplay
+    Cosmetic,
+    /// A [`PhysicsMod::Unknown`]
+    Other,
+}
+
+impl ModCategory {
+    /// Every category, in the order tags are listed
+    pub const ALL: &'static [ModCategory] = &[
+        ModCategory::Movement,
+        ModCategory::Blast,
+        ModCategory::LevelLayout,
+        ModCategory::Powerups,
+        ModCategory::Cosmetic,
+        ModCategory::Other,
+    ];
+
+    /// The tag, like `Level Layout`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModCategory::Movement => "Movement",
+            ModCategory::Blast => "Blast",
+            ModCategory::LevelLayout => "Level Layout",
+            ModCategory::Powerups => "Powerups",
+            ModCategory::Cosmetic => "Cosmetic",
+            ModCategory::Other => "Other",
+        }
+    }
+}
+
+impl Display for ModCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PhysicsMod {
+    /// What part of the game this mod changes
+    pub fn category(&self) -> ModCategory {
+        match self {
+            PhysicsMod::Gravity(_)
+            | PhysicsMod::JumpMult(_)
+            | PhysicsMod::JumpForce(_)
+            | PhysicsMod::BounceMult(_)
+            | PhysicsMod::ScaleMult(_)
+            | PhysicsMod::MassMult(_)
+            | PhysicsMod::FrictionMult(_)
+            | PhysicsMod::RollX(_)
+            | PhysicsMod::RollY(_)
+            | PhysicsMod::AirX(_)
+            | PhysicsMod::AirY(_)
+            | PhysicsMod::AirJumps(_)
+            | PhysicsMod::ImpactX(_)
+            | PhysicsMod::ImpactY(_) => ModCategory::Movement,
+            PhysicsMod::BlastJumpMult(_)
+            | PhysicsMod::BlastPushMult(_)
+            | PhysicsMod::BlastRangeMult(_)
+            | PhysicsMod::BlastCooldownMult(_)
+            | PhysicsMod::CanBlast(_)
+            | PhysicsMod::BlastX(_)
+            | PhysicsMod::BlastY(_) => ModCategory::Blast,
+            PhysicsMod::Reverse(_)
+            | PhysicsMod::CheckpointGems(_)
+            | PhysicsMod::NoGems(_)
+            | PhysicsMod::NoTimeTravel(_)
+            | PhysicsMod::TrophyGem(_)
+            | PhysicsMod::TrophyEnd(_)
+            | PhysicsMod::Boomerang(_)
+            | PhysicsMod::PlatformSpeed(_)
+            | PhysicsMod::MPSpawnOffset(_) => ModCategory::LevelLayout,
+            PhysicsMod::NoPowerups(_)
+            | PhysicsMod::StartPowerup(_)
+            | PhysicsMod::ReplacePowerup(_)
+            | PhysicsMod::MegaForce(_) => ModCategory::Powerups,
+            PhysicsMod::UseSounds(_) | PhysicsMod::FullShadow(_) => ModCategory::Cosmetic,
+            PhysicsMod::Unknown { .. } => ModCategory::Other,
+        }
+    }
+}
@@ -1,4 +1,22 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::MIUError;
+
 /// All the languages for Challenge names
+///
+/// Serializes as the tag the game uses, like `jp` and `zh-CN`.
+/// Parsing also accepts BCP-47 tags and OS locales like `ja`, `zh-Hans`, `pt-BR` and `fr_FR.UTF-8`.
+///
+/// ```
+/// use miu::NameLang;
+///
+/// assert_eq!(NameLang::Jp, "ja".parse().unwrap());
+/// assert_eq!(NameLang::Fr, NameLang::negotiate("de-CH;q=0.5, fr-CH, en;q=0.9"));
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(into = "String", try_from = "String")]
 pub enum NameLang {
     /// English
     En,
@@ -8,30 +26,54 @@ pub enum NameLang {
     Fr,
     /// German
     De,
-    /// Italy
+    /// Italian
     It,
     /// Japanese
     Jp,
     /// Arabic
     Ar,
-    /// Chinese
+    /// Simplified Chinese
     ZhCh,
-    /// Taiwainese
+    /// Traditional Chinese
     ZhTw,
-    /// Netherlands
+    /// Dutch
     Nl,
     /// Korean
     Ko,
-    /// Portgual
+    /// Portuguese
     Pt,
     /// Russian
     Ru,
-    /// Turkey
+    /// Turkish
     Tr,
 }
-impl ToString for NameLang {
-    fn to_string(&self) -> String {
-        String::from(match self {
+
+impl NameLang {
+    const ALL: &'static [NameLang] = &[
+        NameLang::En,
+        NameLang::Es,
+        NameLang::Fr,
+        NameLang::De,
+        NameLang::It,
+        NameLang::Jp,
+        NameLang::Ar,
+        NameLang::ZhCh,
+        NameLang::ZhTw,
+        NameLang::Nl,
+        NameLang::Ko,
+        NameLang::Pt,
+        NameLang::Ru,
+        NameLang::Tr,
+    ];
+
+    /// Every language, English first
+    pub fn all() -> impl Iterator<Item = NameLang> {
+        Self::ALL.iter().copied()
+    }
+
+    /// Returns the tag the game uses, like `jp` or `zh-CN`
+    pub fn as_str(&self) -> &'static str {
+        match self {
             NameLang::En => "en",
             NameLang::Es => "es",
             NameLang::Fr => "fr",
@@ -46,6 +88,178 @@ impl ToString for NameLang {
             NameLang::Pt => "pt",
             NameLang::Ru => "ru",
             NameLang::Tr => "tr",
+        }
+    }
+
+    /// Returns the standard BCP-47 tag, like `ja` or `zh-Hans`
+    pub fn bcp47(&self) -> &'static str {
+        match self {
+            NameLang::Jp => "ja",
+            NameLang::ZhCh => "zh-Hans",
+            NameLang::ZhTw => "zh-Hant",
+            other => other.as_str(),
+        }
+    }
+
+    /// Returns the character written between the whole and decimal part of a number
+    pub fn decimal_separator(&self) -> char {
+        match self {
+            NameLang::Es
+            | NameLang::Fr
+            | NameLang::De
+            | NameLang::It
+            | NameLang::Nl
+            | NameLang::Pt
+            | NameLang::Ru
+            | NameLang::Tr => ',',
+            _ => '.',
+        }
+    }
+
+    /// Picks the best language from an `Accept-Language` header or an OS locale
+    ///
+    /// Languages are tried by their `q` weight, the first one that is supported wins.
+    /// Falls back to English if nothing matches.
+    pub fn negotiate(accept: &str) -> NameLang {
+        let mut candidates = accept
+            .split([',', ':'])
+            .filter_map(|entry| {
+                let mut parts = entry.split(';');
+                let tag = parts.next()?.trim();
+                let weight = parts
+                    .filter_map(|param| param.trim().strip_prefix("q="))
+                    .find_map(|q| q.trim().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+
+                (weight > 0.0).then_some((tag, weight))
+            })
+            .collect::<Vec<(&str, f32)>>();
+        candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        candidates
+            .into_iter()
+            .find_map(|(tag, _)| tag.parse().ok())
+            .unwrap_or(NameLang::En)
+    }
+}
+
+impl FromStr for NameLang {
+    type Err = MIUError;
+
+    /// Accepts the game tags, BCP-47 tags and OS locales, case insensitive
+    fn from_str(tag: &str) -> Result<Self, Self::Err> {
+        let unknown = || MIUError::UnknownLanguage(tag.to_string());
+
+        // `fr_FR.UTF-8@euro` => `fr-fr`
+        let normalized = tag
+            .split(['.', '@'])
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .replace('_', "-")
+            .to_lowercase();
+        let mut subtags = normalized.split('-');
+
+        Ok(match subtags.next().ok_or_else(unknown)? {
+            "en" => NameLang::En,
+            "es" => NameLang::Es,
+            "fr" => NameLang::Fr,
+            "de" => NameLang::De,
+            "it" => NameLang::It,
+            "ja" | "jp" => NameLang::Jp,
+            "ar" => NameLang::Ar,
+            "nl" => NameLang::Nl,
+            "ko" => NameLang::Ko,
+            "pt" => NameLang::Pt,
+            "ru" => NameLang::Ru,
+            "tr" => NameLang::Tr,
+            "zh" => match subtags
+                .find(|subtag| ["hans", "hant", "cn", "sg", "tw", "hk", "mo"].contains(subtag))
+            {
+                Some("hant" | "tw" | "hk" | "mo") => NameLang::ZhTw,
+                _ => NameLang::ZhCh,
+            },
+            _ => return Err(unknown()),
         })
     }
 }
+
+impl TryFrom<String> for NameLang {
+    type Error = MIUError;
+
+    fn try_from(tag: String) -> Result<Self, Self::Error> {
+        tag.parse()
+    }
+}
+
+impl From<NameLang> for String {
+    fn from(lang: NameLang) -> Self {
+        lang.as_str().to_string()
+    }
+}
+
+impl Display for NameLang {
+    /// Formats as the tag the game uses
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::NameLang;
+
+    #[test]
+    fn parse() {
+        for lang in NameLang::all() {
+            assert_eq!(lang, lang.as_str().parse().unwrap());
+            assert_eq!(lang, lang.bcp47().parse().unwrap());
+        }
+
+        for (tag, lang) in [
+            ("ja", NameLang::Jp),
+            ("JA-jp", NameLang::Jp),
+            ("zh-Hans", NameLang::ZhCh),
+            ("zh-Hant-TW", NameLang::ZhTw),
+            ("zh_HK", NameLang::ZhTw),
+            ("zh", NameLang::ZhCh),
+            ("pt-BR", NameLang::Pt),
+            ("fr_FR.UTF-8", NameLang::Fr),
+            ("de_DE@euro", NameLang::De),
+        ] {
+            assert_eq!(lang, tag.parse::<NameLang>().unwrap(), "{tag}");
+        }
+
+        assert!("C".parse::<NameLang>().is_err());
+        assert!("".parse::<NameLang>().is_err());
+        assert!("sv-SE".parse::<NameLang>().is_err());
+        assert_eq!(14, NameLang::all().count());
+    }
+
+    #[test]
+    fn negotiate() {
+        assert_eq!(
+            NameLang::Fr,
+            NameLang::negotiate("fr-CH, fr;q=0.9, en;q=0.8")
+        );
+        assert_eq!(
+            NameLang::Ko,
+            NameLang::negotiate("sv;q=0.9, ko;q=0.5, *;q=0.1")
+        );
+        assert_eq!(NameLang::De, NameLang::negotiate("en;q=0.1, de"));
+        assert_eq!(NameLang::Ru, NameLang::negotiate("ru_RU.UTF-8"));
+        assert_eq!(NameLang::Nl, NameLang::negotiate("nl_BE:nl:en"));
+        assert_eq!(NameLang::En, NameLang::negotiate("fr;q=0, sv"));
+        assert_eq!(NameLang::En, NameLang::negotiate(""));
+    }
+
+    #[test]
+    fn serde() {
+        assert_eq!("\"zh-TW\"", serde_json::to_string(&NameLang::ZhTw).unwrap());
+        assert_eq!(
+            NameLang::Jp,
+            serde_json::from_str::<NameLang>("\"ja\"").unwrap()
+        );
+        assert!(serde_json::from_str::<NameLang>("\"xx\"").is_err());
+    }
+}
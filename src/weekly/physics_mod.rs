@@ -1,9 +1,21 @@
-use serde::{Deserialize, Serialize};
+use std::fmt::Formatter;
+
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{Error, MapAccess, Visitor},
+    ser::SerializeMap,
+};
+use serde_json::Value;
 
 /// All physics mods to ever exist.
 ///
 /// Every mod has a value with it.
+/// Serializes as a single entry map like `{ "gravity": 0.5 }`,
+/// mods that aren't known to this version of the crate become [`PhysicsMod::Unknown`].
+// `remote = "Self"` turns the derives into inherent functions, the trait impls below
+// wrap them to handle `Unknown`
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(remote = "Self")]
 pub enum PhysicsMod {
     /// Changes the gravity
     #[serde(rename = "gravity")]
@@ -150,52 +162,424 @@ pub enum PhysicsMod {
     /// If the Multiplayer spawn offset is enabled
     #[serde(rename = "mpspawnoffset")]
     MPSpawnOffset(bool),
+
+    /// A mod this version of the crate doesn't know, or a known mod with an unexpected value
+    ///
+    /// Kept as is so it serializes back to the same json
+    #[serde(skip)]
+    Unknown {
+        /// The key in the `physicsmod` map
+        key: String,
+        /// The raw value
+        value: Value,
+    },
 }
 
-impl ToString for PhysicsMod {
-    /// Maps the [`PhysicsMod`] to a `Effect: 0%` or `Description` depending on the data within.  
-    fn to_string(&self) -> String {
-        fn float_to_perct(f: &f32) -> String {
-            format!("{}%", f * 100.0)
+impl PhysicsMod {
+    /// Every key this version of the crate knows
+    pub const KEYS: &'static [&'static str] = &[
+        "gravity",
+        "jumpmult",
+        "jumpforce",
+        "bouncemult",
+        "scalemult",
+        "massmult",
+        "frictionmult",
+        "blastjumpmult",
+        "blastpushmult",
+        "blastrangemult",
+        "blastcooldownmult",
+        "rollX",
+        "rollY",
+        "airX",
+        "airY",
+        "canblast",
+        "airjumps",
+        "nopowerups",
+        "reverse",
+        "checkpointgems",
+        "nogems",
+        "notimetravel",
+        "trophygem",
+        "trophyend",
+        "boomerang",
+        "startpowerup",
+        "replacepowerup",
+        "platformspeed",
+        "blastX",
+        "blastY",
+        "impX",
+        "impY",
+        "usesounds",
+        "megaforce",
+        "fullshadow",
+        "mpspawnoffset",
+    ];
+
+    /// The game default of every mod, in the order of [`PhysicsMod::KEYS`]
+    ///
+    /// A level without a mod plays like it had the default, see [`PhysicsMod::is_neutral`].
+    /// [`PhysicsMod::CanBlast`] is listed as `false` but blast is stock in Ultra, so it's never neutral.
+    pub const DEFAULTS: &'static [PhysicsMod] = &[
+        PhysicsMod::Gravity(1.0),
+        PhysicsMod::JumpMult(1.0),
+        PhysicsMod::JumpForce(1.0),
+        PhysicsMod::BounceMult(1.0),
+        PhysicsMod::ScaleMult(1.0),
+        PhysicsMod::MassMult(1.0),
+        PhysicsMod::FrictionMult(1.0),
+        PhysicsMod::BlastJumpMult(1.0),
+        PhysicsMod::BlastPushMult(1.0),
+        PhysicsMod::BlastRangeMult(1.0),
+        PhysicsMod::BlastCooldownMult(1.0),
+        PhysicsMod::RollX(1.0),
+        PhysicsMod::RollY(1.0),
+        PhysicsMod::AirX(1.0),
+        PhysicsMod::AirY(1.0),
+        PhysicsMod::CanBlast(false),
+        PhysicsMod::AirJumps(0),
+        PhysicsMod::NoPowerups(false),
+        PhysicsMod::Reverse(false),
+        PhysicsMod::CheckpointGems(false),
+        PhysicsMod::NoGems(false),
+        PhysicsMod::NoTimeTravel(false),
+        PhysicsMod::TrophyGem(false),
+        PhysicsMod::TrophyEnd(false),
+        PhysicsMod::Boomerang(false),
+        PhysicsMod::StartPowerup(String::new()),
+        PhysicsMod::ReplacePowerup(String::new()),
+        PhysicsMod::PlatformSpeed(1.0),
+        PhysicsMod::BlastX(1.0),
+        PhysicsMod::BlastY(1.0),
+        PhysicsMod::ImpactX(1.0),
+        PhysicsMod::ImpactY(1.0),
+        PhysicsMod::UseSounds(true),
+        PhysicsMod::MegaForce(1.0),
+        PhysicsMod::FullShadow(false),
+        PhysicsMod::MPSpawnOffset(false),
+    ];
+
+    /// Creates a mod from an entry of the `physicsmod` map
+    ///
+    /// Unknown keys and known keys with a value of the wrong type become [`PhysicsMod::Unknown`]
+    pub fn from_entry(key: String, value: Value) -> Self {
+        let entry = Value::Object([(key.clone(), value.clone())].into_iter().collect());
+        PhysicsMod::deserialize(entry).unwrap_or(PhysicsMod::Unknown { key, value })
+    }
+
+    /// Returns the entry of the `physicsmod` map this mod came from
+    pub fn to_entry(&self) -> (String, Value) {
+        if let PhysicsMod::Unknown { key, value } = self {
+            return (key.clone(), value.clone());
+        }
+
+        match PhysicsMod::serialize(self, serde_json::value::Serializer) {
+            Ok(Value::Object(map)) => map.into_iter().next().expect("mods are single entry maps"),
+            _ => unreachable!("known mods always serialize to a single entry map"),
+        }
+    }
+
+    /// Returns the key in the `physicsmod` map, like `gravity`
+    pub fn key(&self) -> &str {
+        match self {
+            PhysicsMod::Unknown { key, .. } => key,
+            _ => {
+                let index = self.index();
+                PhysicsMod::KEYS[index]
+            }
+        }
+    }
+
+    /// Returns the game default for a key, `None` for unknown keys
+    pub fn default_for(key: &str) -> Option<&'static PhysicsMod> {
+        let index = PhysicsMod::KEYS.iter().position(|known| *known == key)?;
+        Some(&PhysicsMod::DEFAULTS[index])
+    }
+
+    /// If the mod is set to its game default, so it doesn't change the level
+    ///
+    /// Unknown mods and [`PhysicsMod::CanBlast`] are never neutral, the blast default depends on the game
+    pub fn is_neutral(&self) -> bool {
+        match self {
+            PhysicsMod::Unknown { .. } | PhysicsMod::CanBlast(_) => false,
+            _ => *self == PhysicsMod::DEFAULTS[self.index()],
+        }
+    }
+
+    /// The value of a float mod like [`PhysicsMod::Gravity`], `None` for other mods
+    pub fn as_f32(&self) -> Option<f32> {
+        match self {
+            PhysicsMod::Gravity(value)
+            | PhysicsMod::JumpMult(value)
+            | PhysicsMod::JumpForce(value)
+            | PhysicsMod::BounceMult(value)
+            | PhysicsMod::ScaleMult(value)
+            | PhysicsMod::MassMult(value)
+            | PhysicsMod::FrictionMult(value)
+            | PhysicsMod::BlastJumpMult(value)
+            | PhysicsMod::BlastPushMult(value)
+            | PhysicsMod::BlastRangeMult(value)
+            | PhysicsMod::BlastCooldownMult(value)
+            | PhysicsMod::RollX(value)
+            | PhysicsMod::RollY(value)
+            | PhysicsMod::AirX(value)
+            | PhysicsMod::AirY(value)
+            | PhysicsMod::PlatformSpeed(value)
+            | PhysicsMod::BlastX(value)
+            | PhysicsMod::BlastY(value)
+            | PhysicsMod::ImpactX(value)
+            | PhysicsMod::ImpactY(value)
+            | PhysicsMod::MegaForce(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The value of a flag mod like [`PhysicsMod::Reverse`], `None` for other mods
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            PhysicsMod::CanBlast(value)
+            | PhysicsMod::NoPowerups(value)
+            | PhysicsMod::Reverse(value)
+            | PhysicsMod::CheckpointGems(value)
+            | PhysicsMod::NoGems(value)
+            | PhysicsMod::NoTimeTravel(value)
+            | PhysicsMod::TrophyGem(value)
+            | PhysicsMod::TrophyEnd(value)
+            | PhysicsMod::Boomerang(value)
+            | PhysicsMod::UseSounds(value)
+            | PhysicsMod::FullShadow(value)
+            | PhysicsMod::MPSpawnOffset(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The value of [`PhysicsMod::AirJumps`], `None` for other mods
+    pub fn as_i32(&self) -> Option<i32> {
+        match self {
+            PhysicsMod::AirJumps(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The powerup of [`PhysicsMod::StartPowerup`] and [`PhysicsMod::ReplacePowerup`], `None` for other mods
+    pub fn as_powerup(&self) -> Option<&str> {
+        match self {
+            PhysicsMod::StartPowerup(value) | PhysicsMod::ReplacePowerup(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns a warning if this mod couldn't be parsed, `None` for known mods
+    pub fn warning(&self) -> Option<String> {
+        match self {
+            PhysicsMod::Unknown { key, value } if PhysicsMod::KEYS.contains(&key.as_str()) => Some(
+                format!("physics mod `{key}` has an unexpected value `{value}`"),
+            ),
+            PhysicsMod::Unknown { key, value } => {
+                Some(format!("unknown physics mod `{key}` with value `{value}`"))
+            }
+            _ => None,
+        }
+    }
+
+    /// The position of the variant in [`PhysicsMod::KEYS`]
+    fn index(&self) -> usize {
+        match self {
+            PhysicsMod::Gravity(_) => 0,
+            PhysicsMod::JumpMult(_) => 1,
+            PhysicsMod::JumpForce(_) => 2,
+            PhysicsMod::BounceMult(_) => 3,
+            PhysicsMod::ScaleMult(_) => 4,
+            PhysicsMod::MassMult(_) => 5,
+            PhysicsMod::FrictionMult(_) => 6,
+            PhysicsMod::BlastJumpMult(_) => 7,
+            PhysicsMod::BlastPushMult(_) => 8,
+            PhysicsMod::BlastRangeMult(_) => 9,
+            PhysicsMod::BlastCooldownMult(_) => 10,
+            PhysicsMod::RollX(_) => 11,
+            PhysicsMod::RollY(_) => 12,
+            PhysicsMod::AirX(_) => 13,
+            PhysicsMod::AirY(_) => 14,
+            PhysicsMod::CanBlast(_) => 15,
+            PhysicsMod::AirJumps(_) => 16,
+            PhysicsMod::NoPowerups(_) => 17,
+            PhysicsMod::Reverse(_) => 18,
+            PhysicsMod::CheckpointGems(_) => 19,
+            PhysicsMod::NoGems(_) => 20,
+            PhysicsMod::NoTimeTravel(_) => 21,
+            PhysicsMod::TrophyGem(_) => 22,
+            PhysicsMod::TrophyEnd(_) => 23,
+            PhysicsMod::Boomerang(_) => 24,
+            PhysicsMod::StartPowerup(_) => 25,
+            PhysicsMod::ReplacePowerup(_) => 26,
+            PhysicsMod::PlatformSpeed(_) => 27,
+            PhysicsMod::BlastX(_) => 28,
+            PhysicsMod::BlastY(_) => 29,
+            PhysicsMod::ImpactX(_) => 30,
+            PhysicsMod::ImpactY(_) => 31,
+            PhysicsMod::UseSounds(_) => 32,
+            PhysicsMod::MegaForce(_) => 33,
+            PhysicsMod::FullShadow(_) => 34,
+            PhysicsMod::MPSpawnOffset(_) => 35,
+            PhysicsMod::Unknown { .. } => unreachable!("unknown mods have their own key"),
         }
+    }
+}
 
+impl Serialize for PhysicsMod {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         match self {
-            PhysicsMod::Gravity(v) => format!("Gravity: {}", float_to_perct(v)),
-            PhysicsMod::JumpMult(v) => format!("Jump Height: {}", float_to_perct(v)),
-            PhysicsMod::JumpForce(v) => format!("Jump Force: {}", float_to_perct(v)),
-            PhysicsMod::BounceMult(v) => format!("Bounce Force: {}", float_to_perct(v)),
-            PhysicsMod::ScaleMult(v) => format!("Marble Size: {}", float_to_perct(v)),
-            PhysicsMod::MassMult(v) => format!("Mass: {}", float_to_perct(v)),
-            PhysicsMod::FrictionMult(v) => format!("Friction Force: {}", float_to_perct(v)),
-            PhysicsMod::BlastJumpMult(v) => format!("Blast Height: {}", float_to_perct(v)),
-            PhysicsMod::BlastPushMult(v) => format!("Blast Push: {}", float_to_perct(v)),
-            PhysicsMod::BlastRangeMult(v) => format!("Blast Range: {}", float_to_perct(v)),
-            PhysicsMod::BlastCooldownMult(v) => format!("Blast Cooldown: {}", float_to_perct(v)),
-            PhysicsMod::RollX(v) => format!("Roll Force X: {}", float_to_perct(v)),
-            PhysicsMod::RollY(v) => format!("Roll Force Y: {}", float_to_perct(v)),
-            PhysicsMod::AirX(v) => format!("Air Force X: {}", float_to_perct(v)),
-            PhysicsMod::AirY(v) => format!("Air Force Y: {}", float_to_perct(v)),
-            PhysicsMod::CanBlast(_) => String::from("Blast Available"),
-            PhysicsMod::AirJumps(v) => format!("Air Jumps: {}", v),
-            PhysicsMod::NoPowerups(_) => String::from("No Powerups"),
-            PhysicsMod::Reverse(_) => String::from("Level Reversed"),
-            PhysicsMod::CheckpointGems(_) => String::from("Checkpoints Add Gems"),
-            PhysicsMod::NoGems(_) => String::from("No Gems"),
-            PhysicsMod::NoTimeTravel(_) => String::from("No Time Travels"),
-            PhysicsMod::TrophyGem(_) => String::from("Trophy Adds Gem"),
-            PhysicsMod::TrophyEnd(_) => String::from("Trophy is Goal"),
-            PhysicsMod::Boomerang(_) => String::from("Boomerang"),
-            PhysicsMod::StartPowerup(v) => format!("Start With: {}", v),
-            PhysicsMod::ReplacePowerup(v) => format!("Replace Powerups: {}", v),
-            PhysicsMod::PlatformSpeed(v) => format!("Platform Speed: {}", float_to_perct(v)),
-            PhysicsMod::BlastX(v) => format!("Blast X: {}", float_to_perct(v)),
-            PhysicsMod::BlastY(v) => format!("Blast Y: {}", float_to_perct(v)),
-            PhysicsMod::ImpactX(v) => format!("Impact X: {}", float_to_perct(v)),
-            PhysicsMod::ImpactY(v) => format!("Impact Y: {}", float_to_perct(v)),
-            PhysicsMod::UseSounds(_) => format!("Use Sounds"),
-            PhysicsMod::MegaForce(v) => format!("Mega Force: {}", float_to_perct(v)),
-            PhysicsMod::FullShadow(_) => format!("Full Shadow"),
-            PhysicsMod::MPSpawnOffset(_) => format!("MP Spawn Offset"),
+            PhysicsMod::Unknown { key, value } => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(key, value)?;
+                map.end()
+            }
+            known => PhysicsMod::serialize(known, serializer),
         }
     }
 }
+
+impl<'de> Deserialize<'de> for PhysicsMod {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut mods = map::deserialize(deserializer)?;
+        match mods.len() {
+            1 => Ok(mods.remove(0)),
+            len => Err(D::Error::invalid_length(len, &"a single physics mod")),
+        }
+    }
+}
+
+/// (De)serializes a list of mods as the `physicsmod` map, `{ "gravity": 0.5, "reverse": true }`
+pub(crate) mod map {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(mods: &[PhysicsMod], serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(mods.len()))?;
+        for physics_mod in mods {
+            let (key, value) = physics_mod.to_entry();
+            map.serialize_entry(&key, &value)?;
+        }
+        map.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<PhysicsMod>, D::Error> {
+        deserializer.deserialize_map(ModsVisitor)
+    }
+
+    struct ModsVisitor;
+
+    impl<'de> Visitor<'de> for ModsVisitor {
+        type Value = Vec<PhysicsMod>;
+
+        fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+            f.write_str("a map of physics mods")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+            let mut mods = Vec::with_capacity(access.size_hint().unwrap_or_default());
+            while let Some((key, value)) = access.next_entry::<String, Value>()? {
+                mods.push(PhysicsMod::from_entry(key, value));
+            }
+            Ok(mods)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::{ChallengeLevel, PhysicsMod, PhysicsModSet};
+
+    #[test]
+    fn unknown_mods() {
+        let level = serde_json::from_str::<ChallengeLevel>(
+            r#"{
+                "name": "Sugar Crash",
+                "id": "SP_sugarRush",
+                "physicsmod": { "gravity": 0.5, "wobble": [1, 2], "airjumps": "many" }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            PhysicsModSet::from(vec![
+                PhysicsMod::Gravity(0.5),
+                PhysicsMod::Unknown {
+                    key: String::from("wobble"),
+                    value: json!([1, 2])
+                },
+                PhysicsMod::Unknown {
+                    key: String::from("airjumps"),
+                    value: json!("many")
+                },
+            ]),
+            level.physicsmod
+        );
+        assert_eq!(2, level.unknown_mods().len());
+        assert_eq!("wobble", level.physicsmod.get("wobble").unwrap().key());
+        assert_eq!(
+            Some(String::from(
+                "physics mod `airjumps` has an unexpected value `\"many\"`"
+            )),
+            level.physicsmod.get("airjumps").unwrap().warning()
+        );
+        assert_eq!(None, level.physicsmod.get("gravity").unwrap().warning());
+
+        let json = serde_json::to_value(&level).unwrap();
+        assert_eq!(
+            json!({ "gravity": 0.5, "wobble": [1, 2], "airjumps": "many" }),
+            json["physicsmod"]
+        );
+    }
+
+    #[test]
+    fn keys() {
+        for key in PhysicsMod::KEYS {
+            let physics_mod = PhysicsMod::from_entry(key.to_string(), json!(false));
+            let physics_mod = match physics_mod {
+                PhysicsMod::Unknown { .. } => PhysicsMod::from_entry(key.to_string(), json!(1)),
+                known => known,
+            };
+            let physics_mod = match physics_mod {
+                PhysicsMod::Unknown { .. } => PhysicsMod::from_entry(key.to_string(), json!("")),
+                known => known,
+            };
+
+            assert_eq!(None, physics_mod.warning(), "{key}");
+            assert_eq!(*key, physics_mod.key());
+            assert_eq!(key.to_string(), physics_mod.to_entry().0);
+            assert_eq!(
+                physics_mod,
+                serde_json::from_value(serde_json::to_value(&physics_mod).unwrap()).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn defaults() {
+        assert_eq!(PhysicsMod::KEYS.len(), PhysicsMod::DEFAULTS.len());
+        for (key, default) in PhysicsMod::KEYS.iter().zip(PhysicsMod::DEFAULTS) {
+            assert_eq!(*key, default.key());
+            assert_eq!(*key != "canblast", default.is_neutral(), "{key}");
+            assert_eq!(Some(default), PhysicsMod::default_for(key));
+        }
+
+        assert!(!PhysicsMod::CanBlast(false).is_neutral());
+        assert!(!PhysicsMod::CanBlast(true).is_neutral());
+        assert!(!PhysicsMod::Gravity(0.5).is_neutral());
+        assert!(!PhysicsMod::from_entry(String::from("wobble"), json!(1)).is_neutral());
+        assert_eq!(None, PhysicsMod::default_for("wobble"));
+
+        assert_eq!(Some(0.5), PhysicsMod::Gravity(0.5).as_f32());
+        assert_eq!(None, PhysicsMod::Reverse(true).as_f32());
+        assert_eq!(Some(true), PhysicsMod::Reverse(true).as_bool());
+        assert_eq!(Some(2), PhysicsMod::AirJumps(2).as_i32());
+        assert_eq!(
+            Some("SuperJump"),
+            PhysicsMod::StartPowerup(String::from("SuperJump")).as_powerup()
+        );
+    }
+}
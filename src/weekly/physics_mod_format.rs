@@ -0,0 +1,409 @@
+use std::fmt::Display;
+
+use crate::{MIUError, NameLang, PhysicsMod};
+
+/// The languages mod labels are translated to, a subset of [`NameLang`]
+///
+/// ```
+/// use miu::{LabelLang, NameLang};
+///
+/// assert_eq!(LabelLang::Fr, LabelLang::try_from(NameLang::Fr).unwrap());
+/// assert!(LabelLang::try_from(NameLang::Jp).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LabelLang {
+    /// English
+    #[default]
+    En,
+    /// Spanish
+    Es,
+    /// French
+    Fr,
+    /// German
+    De,
+}
+
+impl LabelLang {
+    const ALL: &'static [LabelLang] = &[LabelLang::En, LabelLang::Es, LabelLang::Fr, LabelLang::De];
+
+    /// Every language, English first
+    pub fn all() -> impl Iterator<Item = LabelLang> {
+        Self::ALL.iter().copied()
+    }
+}
+
+impl From<LabelLang> for NameLang {
+    fn from(lang: LabelLang) -> Self {
+        match lang {
+            LabelLang::En => NameLang::En,
+            LabelLang::Es => NameLang::Es,
+            LabelLang::Fr => NameLang::Fr,
+            LabelLang::De => NameLang::De,
+        }
+    }
+}
+
+impl TryFrom<NameLang> for LabelLang {
+    type Error = MIUError;
+
+    /// Fails for languages the labels aren't translated to
+    fn try_from(lang: NameLang) -> Result<Self, Self::Error> {
+        LabelLang::all()
+            .find(|label_lang| NameLang::from(*label_lang) == lang)
+            .ok_or_else(|| MIUError::UnknownLanguage(lang.to_string()))
+    }
+}
+
+/// How the value of a float mod is shown, see [`ModFormat`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ValueStyle {
+    /// `150%`
+    #[default]
+    Percent,
+    /// `x1.5`
+    Multiplier,
+}
+
+/// Options for [`PhysicsMod::format`]
+///
+/// ```
+/// use miu::{LabelLang, ModFormat, PhysicsMod};
+///
+/// let gravity = PhysicsMod::Gravity(1.5);
+/// assert_eq!("Gravity: 150%", gravity.to_string());
+/// assert_eq!(Some("+50% Gravity"), gravity.format(&ModFormat::default().delta()).as_deref());
+/// assert_eq!(Some("Gravité: x1,5"), gravity.format(&ModFormat::new(LabelLang::Fr).multiplier()).as_deref());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModFormat {
+    /// The language of the labels and numbers
+    pub lang: LabelLang,
+    /// The most decimals to show, trailing zeros are removed
+    pub precision: usize,
+    /// Percent or multiplier
+    pub style: ValueStyle,
+    /// Show the change from the default instead of the value, like `+50% Gravity`, skipping neutral mods
+    pub delta: bool,
+}
+
+impl ModFormat {
+    /// At most 2 decimals, as percentages
+    pub fn new(lang: LabelLang) -> Self {
+        Self {
+            lang,
+            precision: 2,
+            style: ValueStyle::Percent,
+            delta: false,
+        }
+    }
+
+    /// Sets the most decimals to show
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Shows float mods as multipliers, `x1.5`
+    pub fn multiplier(mut self) -> Self {
+        self.style = ValueStyle::Multiplier;
+        self
+    }
+
+    /// Shows the change from the default, `+50% Gravity`, mods set to their default are skipped
+    pub fn delta(mut self) -> Self {
+        self.delta = true;
+        self
+    }
+
+    /// Formats a number with the precision and decimal separator of the language
+    fn number(&self, value: f64) -> String {
+        let mut number = format!("{value:.0$}", self.precision);
+        if number.contains('.') {
+            number = number
+                .trim_end_matches('0')
+                .trim_end_matches('.')
+                .to_string();
+        }
+        if number == "-0" {
+            number = String::from("0");
+        }
+
+        match NameLang::from(self.lang).decimal_separator() {
+            '.' => number,
+            separator => number.replace('.', &separator.to_string()),
+        }
+    }
+
+    /// Adds a `+` in front of positive numbers
+    fn signed(&self, value: f64) -> String {
+        let number = self.number(value);
+        match number.starts_with('-') {
+            true => number,
+            false => format!("+{number}"),
+        }
+    }
+}
+
+impl Default for ModFormat {
+    fn default() -> Self {
+        Self::new(LabelLang::En)
+    }
+}
+
+impl PhysicsMod {
+    /// Returns the label of the mod, like `Jump Height`
+    ///
+    /// Unknown mods use their key
+    pub fn label(&self, lang: LabelLang) -> &str {
+        let Some((_, en, es, fr, de)) = LABELS.iter().find(|(key, ..)| *key == self.key()) else {
+            return self.key();
+        };
+
+        translate(lang, (en, es, fr, de))
+    }
+
+    /// Formats the mod like `Jump Height: 150%`, `Jump Height: x1.5` or `+50% Jump Height`
+    ///
+    /// Flags are only the label when set, like `No Gems`, and `No Gems: Off` when not.
+    /// Deltas are from the value in [`PhysicsMod::DEFAULTS`],
+    /// `None` in delta mode for mods that don't change anything, see [`PhysicsMod::is_neutral`].
+    pub fn format(&self, format: &ModFormat) -> Option<String> {
+        if format.delta && self.is_neutral() {
+            return None;
+        }
+
+        let label = self.label(format.lang);
+        let default = PhysicsMod::default_for(self.key());
+
+        let multiplier = match self {
+            PhysicsMod::Gravity(v)
+            | PhysicsMod::JumpMult(v)
+            | PhysicsMod::JumpForce(v)
+            | PhysicsMod::BounceMult(v)
+            | PhysicsMod::ScaleMult(v)
+            | PhysicsMod::MassMult(v)
+            | PhysicsMod::FrictionMult(v)
+            | PhysicsMod::BlastJumpMult(v)
+            | PhysicsMod::BlastPushMult(v)
+            | PhysicsMod::BlastRangeMult(v)
+            | PhysicsMod::BlastCooldownMult(v)
+            | PhysicsMod::RollX(v)
+            | PhysicsMod::RollY(v)
+            | PhysicsMod::AirX(v)
+            | PhysicsMod::AirY(v)
+            | PhysicsMod::PlatformSpeed(v)
+            | PhysicsMod::BlastX(v)
+            | PhysicsMod::BlastY(v)
+            | PhysicsMod::ImpactX(v)
+            | PhysicsMod::ImpactY(v)
+            | PhysicsMod::MegaForce(v) => f64::from(*v),
+            PhysicsMod::AirJumps(v) if format.delta => {
+                let from = default.and_then(PhysicsMod::as_i32).unwrap_or_default();
+                return Some(format!("{} {label}", format.signed(f64::from(*v - from))));
+            }
+            PhysicsMod::AirJumps(v) => return Some(format!("{label}: {v}")),
+            PhysicsMod::StartPowerup(v) | PhysicsMod::ReplacePowerup(v) => {
+                return Some(format!("{label}: {v}"));
+            }
+            PhysicsMod::Unknown { value, .. } => return Some(format!("{label}: {value}")),
+            _ => {
+                return Some(match self.as_bool() {
+                    Some(false) => format!("{label}: {}", translate(format.lang, OFF)),
+                    _ => label.to_string(),
+                });
+            }
+        };
+        let from = default.and_then(PhysicsMod::as_f32).map_or(1.0, f64::from);
+
+        let formatted = match (format.style, format.delta) {
+            (ValueStyle::Percent, false) => {
+                format!("{label}: {}%", format.number(multiplier * 100.0))
+            }
+            (ValueStyle::Percent, true) => {
+                format!("{}% {label}", format.signed((multiplier - from) * 100.0))
+            }
+            (ValueStyle::Multiplier, false) => format!("{label}: x{}", format.number(multiplier)),
+            (ValueStyle::Multiplier, true) => {
+                format!("x{} {label}", format.number(multiplier / from))
+            }
+        };
+        Some(formatted)
+    }
+}
+
+impl Display for PhysicsMod {
+    /// Formats with [`ModFormat::default`], like `Jump Height: 150%`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.format(&ModFormat::default()).unwrap_or_default())
+    }
+}
+
+/// Picks the string for a language from `(English, Spanish, French, German)`
+fn translate<'a>(
+    lang: LabelLang,
+    (en, es, fr, de): (&'a str, &'a str, &'a str, &'a str),
+) -> &'a str {
+    match lang {
+        LabelLang::En => en,
+        LabelLang::Es => es,
+        LabelLang::Fr => fr,
+        LabelLang::De => de,
+    }
+}
+
+/// Shown after the label of a flag that isn't set, `English, Spanish, French, German`
+const OFF: (&str, &str, &str, &str) = ("Off", "Desactivado", "Désactivé", "Aus");
+
+/// `key, English, Spanish, French, German`, see [`LabelLang`]
+#[rustfmt::skip]
+const LABELS: &[(&str, &str, &str, &str, &str)] = &[
+    ("gravity", "Gravity", "Gravedad", "Gravité", "Schwerkraft"),
+    ("jumpmult", "Jump Height", "Altura de salto", "Hauteur de saut", "Sprunghöhe"),
+    ("jumpforce", "Jump Force", "Fuerza de salto", "Force de saut", "Sprungkraft"),
+    ("bouncemult", "Bounce Force", "Fuerza de rebote", "Force de rebond", "Abprallkraft"),
+    ("scalemult", "Marble Size", "Tamaño de la canica", "Taille de la bille", "Murmelgröße"),
+    ("massmult", "Mass", "Masa", "Masse", "Masse"),
+    ("frictionmult", "Friction Force", "Fricción", "Friction", "Reibung"),
+    ("blastjumpmult", "Blast Height", "Altura de explosión", "Hauteur d'explosion", "Explosionshöhe"),
+    ("blastpushmult", "Blast Push", "Empuje de explosión", "Poussée d'explosion", "Explosionsschub"),
+    ("blastrangemult", "Blast Range", "Alcance de explosión", "Portée d'explosion", "Explosionsreichweite"),
+    ("blastcooldownmult", "Blast Cooldown", "Recarga de explosión", "Recharge d'explosion", "Explosionsabklingzeit"),
+    ("rollX", "Roll Force X", "Fuerza de rodado X", "Force de roulement X", "Rollkraft X"),
+    ("rollY", "Roll Force Y", "Fuerza de rodado Y", "Force de roulement Y", "Rollkraft Y"),
+    ("airX", "Air Force X", "Fuerza aérea X", "Force aérienne X", "Luftkraft X"),
+    ("airY", "Air Force Y", "Fuerza aérea Y", "Force aérienne Y", "Luftkraft Y"),
+    ("canblast", "Blast Available", "Explosión disponible", "Explosion disponible", "Explosion verfügbar"),
+    ("airjumps", "Air Jumps", "Saltos aéreos", "Sauts aériens", "Luftsprünge"),
+    ("nopowerups", "No Powerups", "Sin potenciadores", "Pas de bonus", "Keine Power-ups"),
+    ("reverse", "Level Reversed", "Nivel invertido", "Niveau inversé", "Level umgekehrt"),
+    ("checkpointgems", "Checkpoints Add Gems", "Los puntos de control añaden gemas", "Les points de contrôle ajoutent des gemmes", "Checkpoints geben Edelsteine"),
+    ("nogems", "No Gems", "Sin gemas", "Pas de gemmes", "Keine Edelsteine"),
+    ("notimetravel", "No Time Travels", "Sin viajes en el tiempo", "Pas de voyages temporels", "Keine Zeitreisen"),
+    ("trophygem", "Trophy Adds Gem", "El trofeo añade una gema", "Le trophée ajoute une gemme", "Trophäe gibt Edelstein"),
+    ("trophyend", "Trophy is Goal", "El trofeo es la meta", "Le trophée est l'arrivée", "Trophäe ist das Ziel"),
+    ("boomerang", "Boomerang", "Bumerán", "Boomerang", "Bumerang"),
+    ("startpowerup", "Start With", "Empieza con", "Commence avec", "Start mit"),
+    ("replacepowerup", "Replace Powerups", "Reemplazar potenciadores", "Remplacer les bonus", "Power-ups ersetzen"),
+    ("platformspeed", "Platform Speed", "Velocidad de plataformas", "Vitesse des plateformes", "Plattformgeschwindigkeit"),
+    ("blastX", "Blast X", "Explosión X", "Explosion X", "Explosion X"),
+    ("blastY", "Blast Y", "Explosión Y", "Explosion Y", "Explosion Y"),
+    ("impX", "Impact X", "Impacto X", "Impact X", "Aufprall X"),
+    ("impY", "Impact Y", "Impacto Y", "Impact Y", "Aufprall Y"),
+    ("usesounds", "Use Sounds", "Usar sonidos", "Utiliser les sons", "Sounds verwenden"),
+    ("megaforce", "Mega Force", "Megafuerza", "Méga force", "Megakraft"),
+    ("fullshadow", "Full Shadow", "Sombra completa", "Ombre complète", "Voller Schatten"),
+    ("mpspawnoffset", "MP Spawn Offset", "Desplazamiento de aparición MP", "Décalage d'apparition MP", "MP-Spawn-Versatz"),
+];
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::{LabelLang, ModFormat, NameLang, PhysicsMod};
+
+    fn delta(physics_mod: PhysicsMod) -> Option<String> {
+        physics_mod.format(&ModFormat::default().delta())
+    }
+
+    #[test]
+    fn format() {
+        let jump = PhysicsMod::JumpMult(1.2);
+        assert_eq!("Jump Height: 120%", jump.to_string());
+        assert_eq!(
+            Some("Jump Height: 120%"),
+            jump.format(&ModFormat::default().precision(4)).as_deref()
+        );
+        assert_eq!(
+            Some("Jump Height: x1.2"),
+            jump.format(&ModFormat::default().multiplier()).as_deref()
+        );
+        assert_eq!(Some("+20% Jump Height"), delta(jump).as_deref());
+        assert_eq!(
+            Some("-66.67% Gravity"),
+            delta(PhysicsMod::Gravity(1.0 / 3.0)).as_deref()
+        );
+        assert_eq!(
+            Some("-67% Gravity"),
+            PhysicsMod::Gravity(1.0 / 3.0)
+                .format(&ModFormat::default().delta().precision(0))
+                .as_deref()
+        );
+
+        assert_eq!("Air Jumps: 2", PhysicsMod::AirJumps(2).to_string());
+        assert_eq!(
+            Some("+2 Air Jumps"),
+            delta(PhysicsMod::AirJumps(2)).as_deref()
+        );
+        assert_eq!("No Gems", PhysicsMod::NoGems(true).to_string());
+        assert_eq!("No Gems: Off", PhysicsMod::NoGems(false).to_string());
+        assert_eq!("Use Sounds", PhysicsMod::UseSounds(true).to_string());
+        assert_eq!(
+            "Start With: SuperJump",
+            PhysicsMod::StartPowerup(String::from("SuperJump")).to_string()
+        );
+        assert_eq!(
+            "wobble: [1,2]",
+            PhysicsMod::from_entry(String::from("wobble"), json!([1, 2])).to_string()
+        );
+
+        // Deltas skip mods that don't change anything
+        assert_eq!(None, delta(PhysicsMod::Gravity(1.0)));
+        assert_eq!(None, delta(PhysicsMod::NoGems(false)));
+        assert_eq!(None, delta(PhysicsMod::AirJumps(0)));
+        assert_eq!(
+            Some("Blast Available: Off"),
+            delta(PhysicsMod::CanBlast(false)).as_deref()
+        );
+        assert_eq!("Gravity: 100%", PhysicsMod::Gravity(1.0).to_string());
+    }
+
+    #[test]
+    fn translated() {
+        let gravity = PhysicsMod::Gravity(0.125);
+        assert_eq!(
+            Some("Schwerkraft: 12,5%"),
+            gravity.format(&ModFormat::new(LabelLang::De)).as_deref()
+        );
+        assert_eq!(
+            Some("-87,5% Gravedad"),
+            gravity
+                .format(&ModFormat::new(LabelLang::Es).delta())
+                .as_deref()
+        );
+        assert_eq!(
+            Some("Pas de gemmes"),
+            PhysicsMod::NoGems(true)
+                .format(&ModFormat::new(LabelLang::Fr))
+                .as_deref()
+        );
+        assert_eq!(
+            Some("Keine Edelsteine: Aus"),
+            PhysicsMod::NoGems(false)
+                .format(&ModFormat::new(LabelLang::De))
+                .as_deref()
+        );
+
+        // Only languages with translated labels can be picked
+        for lang in NameLang::all() {
+            let label_lang = LabelLang::try_from(lang);
+            assert_eq!(
+                matches!(
+                    lang,
+                    NameLang::En | NameLang::Es | NameLang::Fr | NameLang::De
+                ),
+                label_lang.is_ok(),
+                "{lang}"
+            );
+            if let Ok(label_lang) = label_lang {
+                assert_eq!(lang, NameLang::from(label_lang));
+            }
+        }
+
+        for key in PhysicsMod::KEYS {
+            assert!(
+                super::LABELS.iter().any(|(label, ..)| label == key),
+                "{key}"
+            );
+        }
+    }
+}
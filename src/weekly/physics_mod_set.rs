@@ -0,0 +1,489 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{PhysicsMod, weekly::physics_mod};
+
+/// The physics mods of a level, at most one mod per key
+///
+/// Serializes as the `physicsmod` map, `{ "gravity": 0.5, "reverse": true }`, in the original order.
+/// The typed accessors return the game default from [`PhysicsMod::DEFAULTS`] when a mod isn't set.
+///
+/// ```
+/// use miu::{PhysicsMod, PhysicsModSet};
+///
+/// let mods = PhysicsModSet::from_iter([PhysicsMod::Gravity(0.5), PhysicsMod::Reverse(true)]);
+///
+/// assert_eq!(0.5, mods.gravity());
+/// assert_eq!(1.0, mods.jump_mult());
+/// assert!(mods.reverse());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PhysicsModSet {
+    mods: Vec<PhysicsMod>,
+}
+
+/// A difference between two [`PhysicsModSet`]s, see [`PhysicsModSet::diff`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModChange {
+    /// The mod is only in the other set
+    Added(PhysicsMod),
+    /// The mod is only in this set
+    Removed(PhysicsMod),
+    /// Both sets have the mod, with different values
+    Changed {
+        /// The mod in this set
+        from: PhysicsMod,
+        /// The mod in the other set
+        to: PhysicsMod,
+    },
+}
+
+impl PhysicsModSet {
+    /// Creates an empty set, a level without any mods
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The amount of mods
+    pub fn len(&self) -> usize {
+        self.mods.len()
+    }
+
+    /// If no mods are set
+    pub fn is_empty(&self) -> bool {
+        self.mods.is_empty()
+    }
+
+    /// Every mod, in the order of the json
+    pub fn iter(&self) -> std::slice::Iter<'_, PhysicsMod> {
+        self.mods.iter()
+    }
+
+    /// Returns the mod with a key, like `gravity`
+    pub fn get(&self, key: &str) -> Option<&PhysicsMod> {
+        self.mods
+            .iter()
+            .find(|physics_mod| physics_mod.key() == key)
+    }
+
+    /// If the exact mod, with the same value, is in the set
+    pub fn contains(&self, physics_mod: &PhysicsMod) -> bool {
+        self.mods.contains(physics_mod)
+    }
+
+    /// Adds a mod, replacing the mod with the same key and returning it
+    pub fn insert(&mut self, physics_mod: PhysicsMod) -> Option<PhysicsMod> {
+        match self
+            .mods
+            .iter_mut()
+            .find(|existing| existing.key() == physics_mod.key())
+        {
+            Some(existing) => Some(std::mem::replace(existing, physics_mod)),
+            None => {
+                self.mods.push(physics_mod);
+                None
+            }
+        }
+    }
+
+    /// Removes the mod with a key
+    pub fn remove(&mut self, key: &str) -> Option<PhysicsMod> {
+        let index = self
+            .mods
+            .iter()
+            .position(|physics_mod| physics_mod.key() == key)?;
+        Some(self.mods.remove(index))
+    }
+
+    /// Every mod that couldn't be parsed, see [`PhysicsMod::warning`]
+    pub fn unknown(&self) -> impl Iterator<Item = &PhysicsMod> {
+        self.mods
+            .iter()
+            .filter(|physics_mod| matches!(physics_mod, PhysicsMod::Unknown { .. }))
+    }
+
+    /// Lists the mods that are different in `other`
+    ///
+    /// Changed and removed mods are in the order of this set, followed by the added mods.
+    /// A mod set to its default is the same as a missing one, see [`PhysicsMod::is_neutral`].
+    pub fn diff(&self, other: &PhysicsModSet) -> Vec<ModChange> {
+        let mut changes = self
+            .active()
+            .filter_map(|from| match other.get_active(from.key()) {
+                Some(to) if to == from => None,
+                Some(to) => Some(ModChange::Changed {
+                    from: from.clone(),
+                    to: to.clone(),
+                }),
+                None => Some(ModChange::Removed(from.clone())),
+            })
+            .collect::<Vec<ModChange>>();
+
+        changes.extend(
+            other
+                .active()
+                .filter(|to| self.get_active(to.key()).is_none())
+                .map(|to| ModChange::Added(to.clone())),
+        );
+
+        changes
+    }
+
+    /// Every mod that isn't set to its default
+    fn active(&self) -> impl Iterator<Item = &PhysicsMod> {
+        self.mods
+            .iter()
+            .filter(|physics_mod| !physics_mod.is_neutral())
+    }
+
+    /// Returns the mod with a key, `None` if it's missing or set to its default
+    fn get_active(&self, key: &str) -> Option<&PhysicsMod> {
+        self.get(key)
+            .filter(|physics_mod| !physics_mod.is_neutral())
+    }
+
+    /// `gravity`, defaults to `1.0`
+    pub fn gravity(&self) -> f32 {
+        self.value("gravity", PhysicsMod::as_f32)
+    }
+
+    /// `jumpmult`, defaults to `1.0`
+    pub fn jump_mult(&self) -> f32 {
+        self.value("jumpmult", PhysicsMod::as_f32)
+    }
+
+    /// `jumpforce`, defaults to `1.0`
+    pub fn jump_force(&self) -> f32 {
+        self.value("jumpforce", PhysicsMod::as_f32)
+    }
+
+    /// `bouncemult`, defaults to `1.0`
+    pub fn bounce_mult(&self) -> f32 {
+        self.value("bouncemult", PhysicsMod::as_f32)
+    }
+
+    /// `scalemult`, defaults to `1.0`
+    pub fn scale_mult(&self) -> f32 {
+        self.value("scalemult", PhysicsMod::as_f32)
+    }
+
+    /// `massmult`, defaults to `1.0`
+    pub fn mass_mult(&self) -> f32 {
+        self.value("massmult", PhysicsMod::as_f32)
+    }
+
+    /// `frictionmult`, defaults to `1.0`
+    pub fn friction_mult(&self) -> f32 {
+        self.value("frictionmult", PhysicsMod::as_f32)
+    }
+
+    /// `blastjumpmult`, defaults to `1.0`
+    pub fn blast_jump_mult(&self) -> f32 {
+        self.value("blastjumpmult", PhysicsMod::as_f32)
+    }
+
+    /// `blastpushmult`, defaults to `1.0`
+    pub fn blast_push_mult(&self) -> f32 {
+        self.value("blastpushmult", PhysicsMod::as_f32)
+    }
+
+    /// `blastrangemult`, defaults to `1.0`
+    pub fn blast_range_mult(&self) -> f32 {
+        self.value("blastrangemult", PhysicsMod::as_f32)
+    }
+
+    /// `blastcooldownmult`, defaults to `1.0`
+    pub fn blast_cooldown_mult(&self) -> f32 {
+        self.value("blastcooldownmult", PhysicsMod::as_f32)
+    }
+
+    /// `rollX`, defaults to `1.0`
+    pub fn roll_x(&self) -> f32 {
+        self.value("rollX", PhysicsMod::as_f32)
+    }
+
+    /// `rollY`, defaults to `1.0`
+    pub fn roll_y(&self) -> f32 {
+        self.value("rollY", PhysicsMod::as_f32)
+    }
+
+    /// `airX`, defaults to `1.0`
+    pub fn air_x(&self) -> f32 {
+        self.value("airX", PhysicsMod::as_f32)
+    }
+
+    /// `airY`, defaults to `1.0`
+    pub fn air_y(&self) -> f32 {
+        self.value("airY", PhysicsMod::as_f32)
+    }
+
+    /// `platformspeed`, defaults to `1.0`
+    pub fn platform_speed(&self) -> f32 {
+        self.value("platformspeed", PhysicsMod::as_f32)
+    }
+
+    /// `blastX`, defaults to `1.0`
+    pub fn blast_x(&self) -> f32 {
+        self.value("blastX", PhysicsMod::as_f32)
+    }
+
+    /// `blastY`, defaults to `1.0`
+    pub fn blast_y(&self) -> f32 {
+        self.value("blastY", PhysicsMod::as_f32)
+    }
+
+    /// `impX`, defaults to `1.0`
+    pub fn impact_x(&self) -> f32 {
+        self.value("impX", PhysicsMod::as_f32)
+    }
+
+    /// `impY`, defaults to `1.0`
+    pub fn impact_y(&self) -> f32 {
+        self.value("impY", PhysicsMod::as_f32)
+    }
+
+    /// `megaforce`, defaults to `1.0`
+    pub fn mega_force(&self) -> f32 {
+        self.value("megaforce", PhysicsMod::as_f32)
+    }
+
+    /// `airjumps`, defaults to `0`
+    pub fn air_jumps(&self) -> i32 {
+        self.value("airjumps", PhysicsMod::as_i32)
+    }
+
+    /// `canblast`, defaults to `false`
+    pub fn can_blast(&self) -> bool {
+        self.value("canblast", PhysicsMod::as_bool)
+    }
+
+    /// `nopowerups`, defaults to `false`
+    pub fn no_powerups(&self) -> bool {
+        self.value("nopowerups", PhysicsMod::as_bool)
+    }
+
+    /// `reverse`, defaults to `false`
+    pub fn reverse(&self) -> bool {
+        self.value("reverse", PhysicsMod::as_bool)
+    }
+
+    /// `checkpointgems`, defaults to `false`
+    pub fn checkpoint_gems(&self) -> bool {
+        self.value("checkpointgems", PhysicsMod::as_bool)
+    }
+
+    /// `nogems`, defaults to `false`
+    pub fn no_gems(&self) -> bool {
+        self.value("nogems", PhysicsMod::as_bool)
+    }
+
+    /// `notimetravel`, defaults to `false`
+    pub fn no_time_travel(&self) -> bool {
+        self.value("notimetravel", PhysicsMod::as_bool)
+    }
+
+    /// `trophygem`, defaults to `false`
+    pub fn trophy_gem(&self) -> bool {
+        self.value("trophygem", PhysicsMod::as_bool)
+    }
+
+    /// `trophyend`, defaults to `false`
+    pub fn trophy_end(&self) -> bool {
+        self.value("trophyend", PhysicsMod::as_bool)
+    }
+
+    /// `boomerang`, defaults to `false`
+    pub fn boomerang(&self) -> bool {
+        self.value("boomerang", PhysicsMod::as_bool)
+    }
+
+    /// `usesounds`, defaults to `true`
+    pub fn use_sounds(&self) -> bool {
+        self.value("usesounds", PhysicsMod::as_bool)
+    }
+
+    /// `fullshadow`, defaults to `false`
+    pub fn full_shadow(&self) -> bool {
+        self.value("fullshadow", PhysicsMod::as_bool)
+    }
+
+    /// `mpspawnoffset`, defaults to `false`
+    pub fn mp_spawn_offset(&self) -> bool {
+        self.value("mpspawnoffset", PhysicsMod::as_bool)
+    }
+
+    /// `startpowerup`, `None` when starting without a powerup
+    pub fn start_powerup(&self) -> Option<&str> {
+        Some(self.value("startpowerup", PhysicsMod::as_powerup)).filter(|value| !value.is_empty())
+    }
+
+    /// `replacepowerup`, `None` when powerups aren't replaced
+    pub fn replace_powerup(&self) -> Option<&str> {
+        Some(self.value("replacepowerup", PhysicsMod::as_powerup)).filter(|value| !value.is_empty())
+    }
+
+    /// The value of a mod, the [`PhysicsMod::DEFAULTS`] entry if it isn't set or couldn't be parsed
+    fn value<'a, T>(&'a self, key: &str, value: impl Fn(&'a PhysicsMod) -> Option<T>) -> T {
+        self.get(key)
+            .and_then(&value)
+            .or_else(|| PhysicsMod::default_for(key).and_then(&value))
+            .expect("every known key has a default of the same type")
+    }
+}
+
+impl PartialEq for PhysicsModSet {
+    /// Two sets are equal if they have the same mods, in any order
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .mods
+                .iter()
+                .all(|physics_mod| other.contains(physics_mod))
+    }
+}
+
+impl FromIterator<PhysicsMod> for PhysicsModSet {
+    /// Later mods replace earlier mods with the same key
+    fn from_iter<T: IntoIterator<Item = PhysicsMod>>(iter: T) -> Self {
+        let mut set = PhysicsModSet::new();
+        for physics_mod in iter {
+            set.insert(physics_mod);
+        }
+        set
+    }
+}
+
+impl From<Vec<PhysicsMod>> for PhysicsModSet {
+    fn from(mods: Vec<PhysicsMod>) -> Self {
+        mods.into_iter().collect()
+    }
+}
+
+impl IntoIterator for PhysicsModSet {
+    type Item = PhysicsMod;
+    type IntoIter = std::vec::IntoIter<PhysicsMod>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.mods.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a PhysicsModSet {
+    type Item = &'a PhysicsMod;
+    type IntoIter = std::slice::Iter<'a, PhysicsMod>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.mods.iter()
+    }
+}
+
+impl Serialize for PhysicsModSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        physics_mod::map::serialize(&self.mods, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PhysicsModSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        physics_mod::map::deserialize(deserializer).map(PhysicsModSet::from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::{ModChange, PhysicsMod, PhysicsModSet};
+
+    #[test]
+    fn accessors() {
+        let mods = serde_json::from_str::<PhysicsModSet>(
+            r#"{ "reverse": true, "gravity": 0.5, "airjumps": 2, "jumpmult": "high" }"#,
+        )
+        .unwrap();
+
+        assert_eq!(0.5, mods.gravity());
+        assert_eq!(1.0, mods.jump_mult());
+        assert_eq!(1.0, mods.friction_mult());
+        assert_eq!(2, mods.air_jumps());
+        assert!(mods.reverse());
+        assert!(!mods.no_gems());
+        assert!(mods.use_sounds());
+        assert!(!mods.can_blast());
+        assert_eq!(None, mods.start_powerup());
+        assert_eq!(1, mods.unknown().count());
+
+        assert_eq!(
+            r#"{"reverse":true,"gravity":0.5,"airjumps":2,"jumpmult":"high"}"#,
+            serde_json::to_string(&mods).unwrap()
+        );
+    }
+
+    #[test]
+    fn insert_and_diff() {
+        let mut mods = PhysicsModSet::from(vec![
+            PhysicsMod::Gravity(0.5),
+            PhysicsMod::Reverse(true),
+            PhysicsMod::Gravity(0.25),
+        ]);
+        assert_eq!(2, mods.len());
+        assert_eq!(0.25, mods.gravity());
+        assert_eq!(
+            Some(PhysicsMod::Gravity(0.25)),
+            mods.insert(PhysicsMod::Gravity(2.0))
+        );
+
+        let other = PhysicsModSet::from_iter([
+            PhysicsMod::NoGems(true),
+            PhysicsMod::Gravity(0.5),
+            PhysicsMod::Reverse(true),
+        ]);
+        assert_eq!(
+            vec![
+                ModChange::Changed {
+                    from: PhysicsMod::Gravity(2.0),
+                    to: PhysicsMod::Gravity(0.5)
+                },
+                ModChange::Added(PhysicsMod::NoGems(true)),
+            ],
+            mods.diff(&other)
+        );
+        assert_eq!(
+            ModChange::Removed(PhysicsMod::NoGems(true)),
+            other.diff(&mods)[0]
+        );
+
+        assert_eq!(None, mods.insert(PhysicsMod::NoGems(true)));
+        mods.insert(PhysicsMod::Gravity(0.5));
+        assert_eq!(other, mods);
+        assert!(mods.diff(&other).is_empty());
+
+        mods.insert(PhysicsMod::JumpMult(1.0));
+        assert!(mods.diff(&other).is_empty());
+        assert!(other.diff(&mods).is_empty());
+
+        let reset = PhysicsModSet::from_iter([PhysicsMod::Gravity(1.0)]);
+        assert_eq!(
+            vec![
+                ModChange::Removed(PhysicsMod::NoGems(true)),
+                ModChange::Removed(PhysicsMod::Gravity(0.5)),
+                ModChange::Removed(PhysicsMod::Reverse(true)),
+            ],
+            other.diff(&reset)
+        );
+        assert_eq!(
+            vec![
+                ModChange::Added(PhysicsMod::NoGems(true)),
+                ModChange::Added(PhysicsMod::Gravity(0.5)),
+                ModChange::Added(PhysicsMod::Reverse(true)),
+            ],
+            reset.diff(&other)
+        );
+
+        mods.remove("jumpmult");
+        assert_eq!(Some(PhysicsMod::Reverse(true)), mods.remove("reverse"));
+        assert_eq!(
+            json!({ "gravity": 0.5, "nogems": true }),
+            serde_json::to_value(&mods).unwrap()
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::Challenge;
+use crate::{Challenge, WeekState};
 
 /// Holds the current and previous challenge
 ///
@@ -23,3 +23,13 @@ pub struct ScoreBucket {
     /// i honestly dont know what this is help, look at the raw response or something idk
     pub level: String,
 }
+
+impl ScoreBucket {
+    /// Returns the current or previous challenge
+    pub fn get(&self, state: &WeekState) -> &Challenge {
+        match state {
+            WeekState::Current => &self.current,
+            WeekState::Previous => &self.previous,
+        }
+    }
+}